@@ -0,0 +1,157 @@
+use std::time::Instant;
+
+use macroquad::color::Color;
+use macroquad::math::Rect;
+use macroquad::shapes;
+use serde::{Deserialize, Serialize};
+
+const SPEED: f32 = 25.0;
+
+/// Seconds the caret stays on after moving before it starts to blink, and then how long
+/// it's on and off.
+const BLINK: f32 = 0.5;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum CaretStyle {
+    #[default]
+    Line,
+    Block,
+    Underline,
+    Outline,
+    Off,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Caret {
+    #[serde(default)]
+    pub style: CaretStyle,
+
+    #[serde(default = "default_smooth")]
+    pub smooth: bool,
+
+    #[serde(default)]
+    pub blink: bool,
+
+    #[serde(default)]
+    pub color: Option<u32>,
+}
+
+fn default_smooth() -> bool {
+    true
+}
+
+impl Default for Caret {
+    fn default() -> Self {
+        Caret {
+            style: CaretStyle::default(),
+            smooth: default_smooth(),
+            blink: false,
+            color: None,
+        }
+    }
+}
+
+impl Caret {
+    pub fn draw(&self, at: Rect, idle: f32, theme: Color) {
+        let color = self.color.map_or(theme, Color::from_hex);
+
+        if self.blink && idle > BLINK && (((idle - BLINK) / BLINK) as u32).is_multiple_of(2) {
+            return;
+        }
+
+        let thickness = (at.h * 0.08).max(2.0);
+
+        match self.style {
+            CaretStyle::Line => {
+                shapes::draw_rectangle(at.x - thickness / 2.0, at.y, thickness, at.h, color)
+            }
+            CaretStyle::Block => {
+                shapes::draw_rectangle(at.x, at.y, at.w, at.h, Color { a: 0.5, ..color })
+            }
+            CaretStyle::Underline => {
+                shapes::draw_rectangle(at.x, at.y + at.h - thickness, at.w, thickness, color)
+            }
+            CaretStyle::Outline => {
+                shapes::draw_rectangle_lines(at.x, at.y, at.w, at.h, thickness / 2.0, color)
+            }
+            CaretStyle::Off => (),
+        }
+    }
+}
+
+pub struct Motion {
+    at: Option<Rect>,
+    target: Rect,
+    moved: Instant,
+}
+
+impl Motion {
+    pub fn new() -> Self {
+        Motion {
+            at: None,
+            target: Rect::default(),
+            moved: Instant::now(),
+        }
+    }
+
+    pub fn step(&mut self, target: Rect, dt: f32, smooth: bool) -> Rect {
+        if target != self.target {
+            self.target = target;
+            self.moved = Instant::now();
+        }
+
+        let at = match self.at {
+            Some(at) if smooth => {
+                let t = 1.0 - (-dt * SPEED).exp();
+                let close = |from: f32, to: f32| {
+                    if (to - from).abs() < 0.5 {
+                        to
+                    } else {
+                        from + (to - from) * t
+                    }
+                };
+                Rect::new(
+                    close(at.x, target.x),
+                    close(at.y, target.y),
+                    close(at.w, target.w),
+                    close(at.h, target.h),
+                )
+            }
+            _ => target,
+        };
+
+        self.at = Some(at);
+        at
+    }
+
+    pub fn idle(&self) -> f32 {
+        self.moved.elapsed().as_secs_f32()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gliding() {
+        let a = Rect::new(0.0, 0.0, 10.0, 20.0);
+        let b = Rect::new(100.0, 30.0, 12.0, 20.0);
+
+        let mut motion = Motion::new();
+        assert_eq!(motion.step(a, 0.016, true), a);
+        assert_eq!(motion.step(b, 0.016, false), b);
+
+        let mut motion = Motion::new();
+        motion.step(a, 0.016, true);
+        let at = motion.step(b, 0.016, true);
+        assert!(at.x > a.x && at.x < b.x);
+        assert!(at.y > a.y && at.y < b.y);
+
+        // gets there in the end, not just close
+        for _ in 0..60 {
+            motion.step(b, 0.016, true);
+        }
+        assert_eq!(motion.step(b, 0.016, true), b);
+    }
+}
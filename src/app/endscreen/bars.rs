@@ -0,0 +1,142 @@
+use std::rc::Rc;
+
+use macroquad::text::{self, Font};
+use macroquad::{shapes, window};
+
+use crate::app::style::Style;
+use crate::app::text::PrintOptions;
+use crate::app::theme::Theme;
+use crate::app::{self, util, Value};
+
+pub struct Bars {
+    rounds: Vec<(f32, f32)>,
+    pub style: Style,
+    font: Rc<Font>,
+}
+
+impl Bars {
+    pub fn new(style: &Style, rounds: Vec<(f32, f32)>, font: Rc<Font>) -> Self {
+        Bars {
+            rounds,
+            font,
+            style: Style {
+                theme: Theme {
+                    text: Rc::clone(&style.theme.text),
+                    ghost: Rc::clone(&style.theme.ghost),
+                    error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
+                    bg: Rc::clone(&style.theme.bg),
+                },
+                font_size: Rc::clone(&style.font_size),
+                x: Value::Relative(Box::new(|_| {
+                    util::clamp(0.0, window::screen_width() / 10.0, 30.0)
+                })),
+                y: Value::Relative(Box::new(|_| {
+                    util::clamp(10.0, window::screen_height() / 10.0, 30.0)
+                })),
+                width: Value::Relative(Box::new(|this| {
+                    window::screen_width() - 2.0 * this.x.get(this)
+                })),
+                height: Value::Relative(Box::new(|this| {
+                    window::screen_height() / 2.0 - 2.0 * this.y.get(this)
+                })),
+                ..Style::default()
+            },
+        }
+    }
+
+    pub fn update(&self) {
+        if self.rounds.is_empty() {
+            return;
+        }
+
+        let x = self.style.x();
+        let y = self.style.y();
+        let width = self.style.width();
+        let height = self.style.height();
+        let fsize = *self.style.font_size.borrow();
+        let ghost = *self.style.theme.ghost.borrow();
+        let color = *self.style.theme.text.borrow();
+
+        let width_of_space = text::measure_text(" ", Some(&self.font), fsize as u16, 1.0).width;
+        let bottom = y + height - fsize - width_of_space;
+
+        let max_wpm = self
+            .rounds
+            .iter()
+            .map(|(wpm, peak)| wpm.max(*peak))
+            .fold(1.0, f32::max);
+
+        /*
+         * draw the axis and a label of the fastest round
+         * */
+        let label = format!("{:.0}", max_wpm);
+        let w =
+            text::measure_text(&label, Some(&self.font), fsize as u16, 0.8).width + width_of_space;
+
+        app::text::print_text(
+            &self.style,
+            &label,
+            PrintOptions {
+                x: Some(x),
+                y: Some(y - fsize / 2.0),
+                font: Some(Rc::clone(&self.font)),
+                font_scale: Some(0.8),
+                color: Some(ghost),
+                ..PrintOptions::default()
+            },
+        );
+        shapes::draw_line(x + w, y, x + width, y, 2.0, ghost);
+        shapes::draw_line(x + w, bottom, x + width, bottom, 2.0, ghost);
+
+        /*
+         * draw a bar and its round number for each round
+         * */
+        let slot = (width - w) / self.rounds.len() as f32;
+        let bar_width = slot * 0.6;
+
+        for (i, (wpm, peak)) in self.rounds.iter().enumerate() {
+            let center = x + w + slot * (i as f32 + 0.5);
+            let to_y = |wpm: f32| bottom - wpm * (bottom - y) / max_wpm;
+
+            shapes::draw_rectangle(
+                center - bar_width / 2.0,
+                to_y(*wpm),
+                bar_width,
+                bottom - to_y(*wpm),
+                color,
+            );
+            shapes::draw_circle(center, to_y(*peak), 4.0, ghost);
+
+            let round = format!("{}", i + 1);
+            let value = format!("{:.0}", wpm);
+            let round_width = text::measure_text(&round, Some(&self.font), fsize as u16, 0.8).width;
+            let value_dim = text::measure_text(&value, Some(&self.font), fsize as u16, 0.8);
+
+            app::text::print_text(
+                &self.style,
+                &round,
+                PrintOptions {
+                    x: Some(center - round_width / 2.0),
+                    y: Some(y + height - fsize),
+                    font: Some(Rc::clone(&self.font)),
+                    font_scale: Some(0.8),
+                    color: Some(ghost),
+                    ..PrintOptions::default()
+                },
+            );
+            app::text::print_text(
+                &self.style,
+                &value,
+                PrintOptions {
+                    x: Some(center - value_dim.width / 2.0),
+                    y: Some(to_y(*wpm) - value_dim.height - width_of_space),
+                    font: Some(Rc::clone(&self.font)),
+                    font_scale: Some(0.8),
+                    color: Some(ghost),
+                    ..PrintOptions::default()
+                },
+            );
+        }
+    }
+}
@@ -35,6 +35,7 @@ impl Graph {
                     text: Rc::clone(&style.theme.text),
                     ghost: Rc::clone(&style.theme.ghost),
                     error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
                     bg: Rc::clone(&style.theme.bg),
                 },
                 font_size: Rc::clone(&style.font_size),
@@ -56,6 +57,10 @@ impl Graph {
     }
 
     pub fn update(&self) {
+        if self.incremental_wpm.is_empty() {
+            return;
+        }
+
         let x = self.style.x();
         let y = self.style.y();
         let width = self.style.width();
@@ -64,7 +69,12 @@ impl Graph {
 
         let width_of_space = text::measure_text(" ", Some(&self.font), fsize as u16, 1.0).width;
 
-        let wpm_range = Self::range(0.0, self.max_wpm, 5);
+        // a test that ended within a second still needs a range to plot over
+        let max_wpm = self.max_wpm.max(1.0);
+        let start = self.incremental_wpm[0].0;
+        let span = self.time.saturating_sub(start).as_millis().max(1) as f32;
+
+        let wpm_range = Self::range(0.0, max_wpm, 5);
         let wpm_y_range = Self::range(y, y + height - fsize - width_of_space, 5);
 
         /*
@@ -75,7 +85,7 @@ impl Graph {
 
         for _wpm in &wpm_range {
             let _w =
-                text::measure_text(&format!("{:.0}", _wpm), Some(&self.font), fsize as u16, 0.8)
+                text::measure_text(format!("{:.0}", _wpm), Some(&self.font), fsize as u16, 0.8)
                     .width;
 
             offset_xs.push(_w);
@@ -117,16 +127,14 @@ impl Graph {
          * */
         w += width_of_space;
 
-        let time_x_range: Vec<f32> = (self.incremental_wpm[0].0.as_secs()..=self.time.as_secs())
-            .map(|s| {
-                (1000.0 * (s - 1) as f32 / (self.time.as_millis() - 1000) as f32) * (width - w)
-                    + x
-                    + w
-            })
+        let to_x =
+            |t: Duration| t.saturating_sub(start).as_millis() as f32 / span * (width - w) + x + w;
+
+        let time_x_range: Vec<f32> = (start.as_secs()..=self.time.as_secs())
+            .map(|s| to_x(Duration::from_secs(s)))
             .collect();
 
-        let time_range: Vec<u64> =
-            (self.incremental_wpm[0].0.as_secs()..=self.time.as_secs()).collect();
+        let time_range: Vec<u64> = (start.as_secs()..=self.time.as_secs()).collect();
 
         let mut step_by = 1;
         while step_by < time_x_range.len()
@@ -168,13 +176,10 @@ impl Graph {
         let mut last_x = -1.0;
         let mut last_y = -1.0;
         for (i, (t, wpm)) in self.incremental_wpm.iter().enumerate() {
-            let x_p = (t.as_millis() - 1000) as f32 * (width - w)
-                / (self.time.as_millis() - 1000) as f32
-                + x
-                + w;
+            let x_p = to_x(*t);
 
             let y_p = (height - fsize - width_of_space)
-                - *wpm * (height - fsize - width_of_space) / self.max_wpm
+                - *wpm * (height - fsize - width_of_space) / max_wpm
                 + y;
 
             shapes::draw_circle(x_p, y_p, 4.0, *self.style.theme.text.borrow());
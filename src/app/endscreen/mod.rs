@@ -1,40 +1,62 @@
 use std::process;
 use std::rc::Rc;
+use std::time::Duration;
 
-use macroquad::input::{self, KeyCode, MouseButton};
-use macroquad::math::Vec2;
+use macroquad::input;
 use macroquad::window;
 
+mod bars;
 mod graph;
-mod next_button;
-mod quit_button;
-mod restart_button;
 mod source;
 mod wpm;
 
-use super::focus::{EndscreenFocus::*, Focus};
-use super::{util, App, Screen, Value};
+use super::events::Event;
+use super::focus::{EndscreenFocus, Focus, Nav};
+use super::widget::Widget;
+use super::{Action, App, Mode, Screen};
+use crate::{RunResult, Summary};
 
 pub async fn run(app: &mut App) {
     input::show_mouse(true);
     input::clear_input_queue();
+    app.events.drain();
 
-    let mut focus = Nothing;
-
-    let wpm = wpm::Wpm::new(
+    let mut wpm = wpm::Wpm::new(
         &app.style,
         app.state.wpm,
         app.state.accuracy,
         app.state.time,
+        app.state.history.last().and_then(|r| r.failed.clone()),
         Rc::clone(&app.font),
     );
 
-    let mut next_button = next_button::NextButton::new(&app.style, Rc::clone(&app.font));
-    let mut quit_button = quit_button::QuitButton::new(&app.style, Rc::clone(&app.font));
-    let mut restart_button = restart_button::RestartButton::new(&app.style, Rc::clone(&app.font));
+    // only offered when there's something to practice
+    let can_practice = !app.state.missed.is_empty();
+
+    let button = |id, text| Widget::button(id, text, &app.style, Rc::clone(&app.font));
+    let mut buttons = Widget::row(
+        &app.style,
+        0.0,
+        [
+            Some(button(EndscreenFocus::Next, "Next (n)")),
+            Some(button(EndscreenFocus::Restart, "Restart (r)")),
+            can_practice.then(|| button(EndscreenFocus::Practice, "Practice (p)")),
+            Some(button(EndscreenFocus::Quit, "Quit (q)")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+    );
 
-    let source = source::Source::new(&app.style, app.state.mode.to_string(), Rc::clone(&app.font));
-    let graph = graph::Graph::new(
+    let mut source_text = match (&app.state.mode, app.state.seed) {
+        (Mode::Daily { .. } | Mode::Lesson { .. } | Mode::Drill { .. } | Mode::Zen { .. }, _)
+        | (_, None) => app.state.mode.to_string(),
+        (mode, Some(seed)) => format!("{} (seed {})", mode, seed),
+    };
+    if let Some(note) = app.finish() {
+        source_text = format!("{}, {}", source_text, note);
+    }
+    let mut graph = graph::Graph::new(
         &app.style,
         app.state.incremental_wpm.clone(),
         app.state.max_wpm,
@@ -42,119 +64,280 @@ pub async fn run(app: &mut App) {
         Rc::clone(&app.font),
     );
 
+    // the results of each test of a session, in place of the source once it's over
+    let mut table = None;
+    if let Some(runs) = app.session_runs() {
+        if app.session_over() {
+            // the summary takes the place of the last test, whose wpm is in it
+            let summary = Summary::new(runs);
+
+            wpm = wpm::Wpm::with_stats(
+                &app.style,
+                vec![
+                    ("MEAN", summary.as_ref().map_or(0.0, |s| s.mean)),
+                    ("MEDIAN", summary.as_ref().map_or(0.0, |s| s.median)),
+                    ("BEST", summary.as_ref().map_or(0.0, |s| s.best)),
+                ]
+                .into_iter()
+                .map(|(k, v)| (k, format!("{:.1}", v)))
+                .chain([("TESTS", runs.len().to_string())])
+                .collect(),
+                None,
+                Rc::clone(&app.font),
+            );
+            // accuracy of each test, numbered from 1
+            graph = graph::Graph::new(
+                &app.style,
+                runs.iter()
+                    .enumerate()
+                    .map(|(i, r)| (Duration::from_secs(i as u64 + 1), r.accuracy as f32))
+                    .collect(),
+                100.0,
+                Duration::from_secs(runs.len() as u64),
+                Rc::clone(&app.font),
+            );
+            table = Some(session_table(app, runs));
+
+            app.state.session = None;
+        } else {
+            let test = runs.len();
+            let progress = match (app.config.session.minutes, &app.state.session) {
+                (Some(minutes), Some(session)) => format!(
+                    "Test {}, {} min left",
+                    test,
+                    (minutes * 60).saturating_sub(session.started.elapsed().as_secs()) / 60 + 1
+                ),
+                _ => format!("Test {} of {}", test, app.config.session.tests),
+            };
+            source_text = format!("{}, {}", progress, source_text);
+        }
+    }
+
+    // the last sprint of a training shows how every round went instead of itself
+    let mut bars = None;
+    if matches!(app.state.mode, Mode::Sprint { .. }) && !app.state.sprints.is_empty() {
+        let sprints = std::mem::take(&mut app.state.sprints);
+        let rounds = sprints.len() as f32;
+
+        wpm = wpm::Wpm::with_stats(
+            &app.style,
+            vec![
+                (
+                    "WPM",
+                    format!("{:.1}", sprints.iter().map(|s| s.wpm).sum::<f32>() / rounds),
+                ),
+                (
+                    "ACC",
+                    format!(
+                        "{:.0}%",
+                        sprints.iter().map(|s| s.accuracy as f32).sum::<f32>() / rounds
+                    ),
+                ),
+                (
+                    "BEST",
+                    format!("{:.1}", sprints.iter().map(|s| s.wpm).fold(0.0, f32::max)),
+                ),
+                ("ROUNDS", sprints.len().to_string()),
+            ],
+            None,
+            Rc::clone(&app.font),
+        );
+        bars = Some(bars::Bars::new(
+            &app.style,
+            sprints
+                .iter()
+                .map(|s| {
+                    let peak = s.samples.iter().map(|(_, w)| *w).fold(s.wpm, f32::max);
+                    (s.wpm, peak)
+                })
+                .collect(),
+            Rc::clone(&app.font),
+        ));
+        source_text = format!(
+            "{} rounds of {}s, {}s rest",
+            sprints.len(),
+            app.config.sprint.seconds,
+            app.config.sprint.rest
+        );
+    }
+    let source = source::Source::new(&app.style, source_text, Rc::clone(&app.font));
+
+    let mut focus = Focus::new(
+        [
+            Some(EndscreenFocus::Next),
+            Some(EndscreenFocus::Restart),
+            can_practice.then_some(EndscreenFocus::Practice),
+            Some(EndscreenFocus::Quit),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        None,
+    )
+    .with_arrows();
+
     loop {
-        if let Some(k) = input::get_last_key_pressed() {
-            match k {
-                KeyCode::Tab => focus.next(),
-                KeyCode::Enter => match focus {
-                    NextButton => {
-                        app.state.mode.next(&app.data);
-                        app.state.screen = Screen::TypingTest;
-                        return;
-                    }
-                    RestartButton => {
-                        app.state.screen = Screen::TypingTest;
-                        return;
-                    }
-                    QuitButton => process::exit(0),
-                    _ => (),
-                },
-                KeyCode::Equal
-                    if (input::is_key_down(KeyCode::LeftSuper)
-                        || input::is_key_down(KeyCode::RightSuper)) =>
-                {
-                    input::clear_input_queue();
-                    *app.style.font_size.borrow_mut() += 5.0;
+        buttons.center(window::screen_height() / 2.0 + 3.0 * *app.style.font_size.borrow());
+        if let Some(table) = &mut table {
+            table.center(window::screen_height() - table.size().y - 40.0);
+        }
+
+        let events = app.read_events();
+        if app.state.screen != Screen::End {
+            return;
+        }
+        for (event, _) in events {
+            match focus.handle(event, &app.config.keybindings) {
+                Nav::Moved => continue,
+                Nav::Activated(id) => {
+                    press(app, id);
+                    return;
                 }
-                KeyCode::Minus
-                    if (input::is_key_down(KeyCode::LeftSuper)
-                        || input::is_key_down(KeyCode::RightSuper)) =>
-                {
-                    input::clear_input_queue();
-                    *app.style.font_size.borrow_mut() -= 5.0;
+                Nav::Ignored => (),
+            }
+
+            let action = match event {
+                Event::Key(key, mods) => app.config.keybindings.action(key, mods),
+                Event::Action(action) => Some(action),
+                Event::Char(_) => continue,
+            };
+
+            match action {
+                Some(Action::Next) => {
+                    next(app);
+                    return;
                 }
-                KeyCode::Key0
-                    if (input::is_key_down(KeyCode::LeftSuper)
-                        || input::is_key_down(KeyCode::RightSuper)) =>
-                {
-                    input::clear_input_queue();
-                    *app.style.font_size.borrow_mut() = app.config.font_size;
+                Some(Action::Restart) => {
+                    restart(app);
+                    return;
                 }
-                _ => {
-                    if let Some(c) = input::get_char_pressed() {
-                        match c {
-                            'n' => {
-                                app.state.mode.next(&app.data);
-                                app.state.screen = Screen::TypingTest;
-                                return;
-                            }
-                            'r' => {
-                                app.state.screen = Screen::TypingTest;
-                                return;
-                            }
-                            'q' => process::exit(0),
-                            _ => (),
-                        }
-                    }
+                Some(Action::Practice) if can_practice => {
+                    practice(app);
+                    return;
                 }
-            }
-        }
-
-        if input::is_mouse_button_pressed(MouseButton::Left) {
-            match focus {
-                NextButton => {
-                    app.state.mode.next(&app.data);
-                    app.state.screen = Screen::TypingTest;
+                Some(Action::Quit) => process::exit(0),
+                Some(Action::Theme) => {
+                    app.state.screen = Screen::ThemeSelect;
+                    return;
+                }
+                Some(Action::Settings) => {
+                    app.state.screen = Screen::Settings;
                     return;
                 }
-                RestartButton => {
+                Some(Action::Search) => {
+                    app.state.screen = Screen::QuoteSearch;
+                    return;
+                }
+                Some(Action::Lessons) => {
+                    app.state.screen = Screen::Lessons;
+                    return;
+                }
+                Some(Action::Session) => {
+                    app.start_session();
                     app.state.screen = Screen::TypingTest;
                     return;
                 }
-                QuitButton => process::exit(0),
-                _ => (),
+                Some(action) => app.font_action(action),
+                None => (),
             }
         }
 
-        match input::mouse_delta_position() {
-            Vec2 { x: dx, y: dy } if dx != 0.0 && dy != 0.0 => {
-                focus = if util::is_hover(&next_button.style) {
-                    NextButton
-                } else if util::is_hover(&quit_button.style) {
-                    QuitButton
-                } else if util::is_hover(&restart_button.style) {
-                    RestartButton
-                } else {
-                    Nothing
-                }
-            }
-            _ => (),
+        if let Some(id) = focus.mouse(buttons.hovered()) {
+            press(app, id);
+            return;
         }
-        let width =
-            next_button.style.width() + quit_button.style.width() + restart_button.style.width();
-        let x_start = (window::screen_width() - width) / 2.0;
-
-        next_button.style.x = Value::Absolute(x_start);
-        restart_button.style.x = Value::Absolute(x_start + next_button.style.width());
-        quit_button.style.x =
-            Value::Absolute(x_start + next_button.style.width() + restart_button.style.width());
-
         window::clear_background(*app.style.theme.bg.borrow());
 
-        next_button.update();
-        restart_button.update();
-        quit_button.update();
+        buttons.draw(focus.get());
         wpm.update();
-        source.update();
-        graph.update();
-
-        match focus {
-            QuitButton => quit_button.style.draw_border(),
-            NextButton => next_button.style.draw_border(),
-            RestartButton => restart_button.style.draw_border(),
-            _ => (),
+        match &table {
+            Some(table) => table.draw(None),
+            None => source.update(),
         }
+        match &bars {
+            Some(bars) => bars.update(),
+            None => graph.update(),
+        }
+
+        app.palette.update();
 
         window::next_frame().await;
     }
 }
+
+fn press(app: &mut App, id: EndscreenFocus) {
+    match id {
+        EndscreenFocus::Next => next(app),
+        EndscreenFocus::Restart => restart(app),
+        EndscreenFocus::Practice => practice(app),
+        EndscreenFocus::Quit => process::exit(0),
+    }
+}
+
+/// Types the same text again. In a session that's the same test, so the result it had
+/// is dropped for the new one.
+fn restart(app: &mut App) {
+    if let Some(session) = &mut app.state.session {
+        if !matches!(app.state.mode, Mode::Missed { .. }) {
+            session.runs.pop();
+        }
+    }
+    app.state.screen = Screen::TypingTest;
+}
+
+fn next(app: &mut App) {
+    if app.state.session.is_some() {
+        app.next_session_test();
+    } else {
+        app.next_text();
+    }
+    app.state.screen = Screen::TypingTest;
+}
+
+fn practice(app: &mut App) {
+    app.state.mode = Mode::Missed {
+        words: app.state.missed.clone(),
+        repeat: app.config.practice.repeat,
+        s: "".to_string(),
+    };
+    app.next_text();
+    app.state.screen = Screen::TypingTest;
+}
+
+fn session_table(app: &App, runs: &[RunResult]) -> Widget<EndscreenFocus> {
+    let label = |text: &str, color| Widget::label(text, color, &app.style, Rc::clone(&app.font));
+    let column = |cells| Widget::column(&app.style, 0.3, cells);
+
+    let header = Widget::stack(
+        &app.style,
+        0.3,
+        ["TEST", "MODE", "WPM", "ACC"]
+            .map(|text| label(text, &app.style.theme.ghost))
+            .into(),
+    );
+    let tests = runs.iter().enumerate().map(|(i, run)| {
+        // a quote is named after its source, which can be long
+        let mode = if run.mode.chars().count() > 12 {
+            format!("{}...", run.mode.chars().take(10).collect::<String>())
+        } else {
+            run.mode.clone()
+        };
+
+        column(
+            [
+                (i + 1).to_string(),
+                mode,
+                format!("{:.1}", run.wpm),
+                format!("{}%", run.accuracy),
+            ]
+            .map(|text| label(&text, &app.style.theme.text))
+            .into(),
+        )
+    });
+
+    Widget::row(
+        &app.style,
+        1.0,
+        std::iter::once(header).chain(tests).collect(),
+    )
+}
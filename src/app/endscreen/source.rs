@@ -28,6 +28,7 @@ impl Source {
                     text: Rc::clone(&style.theme.ghost),
                     ghost: Rc::clone(&style.theme.ghost),
                     error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
                 },
                 font_size: Rc::clone(&style.font_size),
                 width: Value::Relative(Box::new(move |_| window::screen_width())),
@@ -10,18 +10,38 @@ use crate::app::{theme::Theme, Style, Value};
 pub struct Wpm {
     pub style: Style,
     font: Rc<Font>,
-    wpm: f32,
-    accuracy: i32,
-    time: Duration,
+    stats: Vec<(&'static str, String)>,
+    failed: Option<String>,
 }
 
 impl Wpm {
-    pub fn new(style: &Style, wpm: f32, accuracy: i32, time: Duration, font: Rc<Font>) -> Wpm {
+    pub fn new(
+        style: &Style,
+        wpm: f32,
+        accuracy: i32,
+        time: Duration,
+        failed: Option<String>,
+        font: Rc<Font>,
+    ) -> Wpm {
+        let stats = vec![
+            ("WPM", format!("{:.1}", wpm)),
+            ("ACC", format!("{}%", accuracy)),
+            ("TIME", format!("{:.1}s", time.as_millis() as f32 / 1000.0)),
+        ];
+
+        Wpm::with_stats(style, stats, failed, font)
+    }
+
+    pub fn with_stats(
+        style: &Style,
+        stats: Vec<(&'static str, String)>,
+        failed: Option<String>,
+        font: Rc<Font>,
+    ) -> Wpm {
         Wpm {
             font,
-            wpm,
-            accuracy,
-            time,
+            stats,
+            failed,
             style: Style {
                 y: Value::Relative(Box::new(move |_| window::screen_height() / 2.0)),
                 font_size: Rc::clone(&style.font_size),
@@ -30,6 +50,7 @@ impl Wpm {
                     ghost: Rc::clone(&style.theme.ghost),
                     text: Rc::clone(&style.theme.text),
                     error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
                 },
                 ..Style::default()
             },
@@ -37,22 +58,30 @@ impl Wpm {
     }
 
     pub fn update(&self) {
-        let stats = [
-            ("WPM", format!("{:.1}", self.wpm)),
-            ("ACC", format!("{}%", self.accuracy)),
-            (
-                "TIME",
-                format!("{:.1}s", self.time.as_millis() as f32 / 1000.0),
-            ),
-        ];
-
-        let (xs, width) = self.measure_text(&stats);
+        let (xs, width) = self.measure_text(&self.stats);
         let margin_x = (window::screen_width() - width) / 2.0;
 
         let fsize = *self.style.font_size.borrow();
         let fscale = 0.65;
 
-        for ((key, value), x) in stats.iter().zip(xs) {
+        if let Some(reason) = &self.failed {
+            let failed = format!("FAILED, {}", reason);
+            let width = text::measure_text(&failed, Some(&self.font), fsize as u16, 1.0).width;
+
+            crate::app::text::print_text(
+                &self.style,
+                &failed,
+                PrintOptions {
+                    font: Some(Rc::clone(&self.font)),
+                    x: Some((window::screen_width() - width) / 2.0),
+                    y: Some(self.style.y() - 2.0 * fsize),
+                    color: Some(*self.style.theme.error.borrow()),
+                    ..PrintOptions::default()
+                },
+            );
+        }
+
+        for ((key, value), x) in self.stats.iter().zip(xs) {
             crate::app::text::print_text(
                 &self.style,
                 key,
@@ -84,7 +113,7 @@ impl Wpm {
         let fsize = *self.style.font_size.borrow();
         let fscale = 0.65;
 
-        let mut xs = Vec::with_capacity(3);
+        let mut xs = Vec::with_capacity(stats.len());
 
         for (i, (key, value)) in stats.iter().enumerate() {
             xs.push(x);
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use macroquad::input::utils::{register_input_subscriber, repeat_all_miniquad_input};
+use macroquad::input::KeyCode;
+use macroquad::miniquad::{EventHandler, KeyMods};
+
+use super::{Action, Layout};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Event {
+    Key(KeyCode, KeyMods),
+    Char(char),
+    Action(Action),
+}
+
+/// Every key and character event of the window since the last `drain`, in the order
+/// they came in. Unlike `get_last_key_pressed` and `get_char_pressed`, which hand out
+/// one of each a frame, nothing is dropped or reordered when several keys are typed in a
+/// single frame.
+pub struct Events {
+    subscriber: usize,
+    queue: Queue,
+}
+
+impl Events {
+    pub fn new(layout: &Layout) -> Self {
+        Events {
+            subscriber: register_input_subscriber(),
+            queue: Queue {
+                remap: layout.remap(),
+                ..Queue::default()
+            },
+        }
+    }
+
+    pub fn drain(&mut self) -> Vec<(Event, Instant)> {
+        repeat_all_miniquad_input(&mut self.queue, self.subscriber);
+        self.queue.take()
+    }
+
+    pub fn set_layout(&mut self, layout: &Layout) {
+        self.queue.remap = layout.remap();
+    }
+}
+
+#[derive(Default)]
+struct Queue {
+    events: Vec<(Event, Instant)>,
+    remap: HashMap<char, char>,
+}
+
+impl Queue {
+    fn take(&mut self) -> Vec<(Event, Instant)> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+impl EventHandler for Queue {
+    fn update(&mut self) {}
+
+    fn draw(&mut self) {}
+
+    fn char_event(&mut self, character: char, keymods: KeyMods, _repeat: bool) {
+        // Enter, Tab and Backspace come as keys, and with Ctrl or Super held it's a
+        // shortcut rather than something typed. AltGr comes as Ctrl+Alt on Windows and
+        // some X11 setups though, and types the @ { [ \ and € of many layouts.
+        if character.is_control() || (keymods.ctrl && !keymods.alt) || keymods.logo {
+            return;
+        }
+        let character = self.remap.get(&character).copied().unwrap_or(character);
+        self.events.push((Event::Char(character), Instant::now()));
+    }
+
+    fn key_down_event(&mut self, keycode: KeyCode, keymods: KeyMods, _repeat: bool) {
+        self.events
+            .push((Event::Key(keycode, keymods), Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::LayoutName;
+
+    #[test]
+    fn nothing_lost() {
+        let text = "the quick brown fox jumps over the lazy dog ".repeat(500);
+        let none = KeyMods::default();
+        let mut queue = Queue::default();
+        let mut expected = vec![];
+
+        // far more than any frame sees, with a backspace, a shortcut and the control
+        // characters of some keys in between
+        for (i, c) in text.chars().enumerate() {
+            queue.key_down_event(KeyCode::A, none, false);
+            queue.char_event(c, none, false);
+            expected.push(Event::Key(KeyCode::A, none));
+            expected.push(Event::Char(c));
+
+            if i % 7 == 0 {
+                queue.key_down_event(KeyCode::Backspace, none, false);
+                queue.char_event('\u{8}', none, false);
+                expected.push(Event::Key(KeyCode::Backspace, none));
+            }
+            if i % 11 == 0 {
+                let logo = KeyMods { logo: true, ..none };
+                queue.key_down_event(KeyCode::Equal, logo, false);
+                queue.char_event('=', logo, false);
+                expected.push(Event::Key(KeyCode::Equal, logo));
+            }
+            if i % 13 == 0 {
+                let altgr = KeyMods {
+                    ctrl: true,
+                    alt: true,
+                    ..none
+                };
+                queue.key_down_event(KeyCode::Q, altgr, false);
+                queue.char_event('@', altgr, false);
+                expected.push(Event::Key(KeyCode::Q, altgr));
+                expected.push(Event::Char('@'));
+            }
+        }
+
+        let events = queue.take();
+        assert_eq!(
+            events.iter().map(|(e, _)| *e).collect::<Vec<Event>>(),
+            expected
+        );
+        assert!(events.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(queue.take().is_empty());
+    }
+
+    #[test]
+    fn remapped() {
+        let none = KeyMods::default();
+        let mut queue = Queue {
+            remap: Layout::builtin(LayoutName::Dvorak).remap(),
+            ..Queue::default()
+        };
+
+        for c in "Sdf;".chars() {
+            queue.char_event(c, none, false);
+        }
+        // AltGr on a German layout
+        let altgr = KeyMods {
+            ctrl: true,
+            alt: true,
+            ..none
+        };
+        queue.char_event('€', altgr, false);
+        queue.char_event('s', KeyMods { ctrl: true, ..none }, false);
+
+        assert_eq!(
+            queue.take().iter().map(|(e, _)| *e).collect::<Vec<Event>>(),
+            "Oeus€".chars().map(Event::Char).collect::<Vec<Event>>()
+        );
+    }
+}
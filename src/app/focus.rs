@@ -1,43 +1,203 @@
-pub trait Focus {
-    fn next(&mut self);
-}
+use macroquad::input::{self, KeyCode, MouseButton};
+
+use super::events::Event;
+use super::{Action, Keybindings};
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy, Debug)]
 pub enum TypingTestFocus {
     RestartButton,
     NextButton,
     ThemeButton,
+    SearchButton,
     TypingBox,
-    Nothing,
 }
 
-impl Focus for TypingTestFocus {
-    fn next(&mut self) {
-        match self {
-            TypingTestFocus::Nothing => *self = TypingTestFocus::NextButton,
-            TypingTestFocus::NextButton => *self = TypingTestFocus::RestartButton,
-            TypingTestFocus::RestartButton => *self = TypingTestFocus::ThemeButton,
-            TypingTestFocus::ThemeButton => *self = TypingTestFocus::NextButton,
-            TypingTestFocus::TypingBox => *self = TypingTestFocus::NextButton,
-        }
-    }
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum EndscreenFocus {
+    Next,
+    Restart,
+    Practice,
+    Quit,
 }
 
-#[derive(PartialEq)]
-pub enum EndscreenFocus {
-    NextButton,
-    QuitButton,
-    RestartButton,
-    Nothing,
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum QuoteSearchFocus {
+    SearchBox,
+    Results,
+    CancelButton,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum LessonsFocus {
+    Start,
+    Reset,
+    Cancel,
 }
 
-impl Focus for EndscreenFocus {
-    fn next(&mut self) {
-        match self {
-            EndscreenFocus::Nothing => *self = EndscreenFocus::NextButton,
-            EndscreenFocus::NextButton => *self = EndscreenFocus::RestartButton,
-            EndscreenFocus::RestartButton => *self = EndscreenFocus::QuitButton,
-            EndscreenFocus::QuitButton => *self = EndscreenFocus::NextButton,
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ThemeSelectFocus {
+    Theme(usize),
+    CancelButton,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Nav<T> {
+    Moved,
+    Activated(T),
+    Ignored,
+}
+
+pub struct Focus<T> {
+    order: Vec<T>,
+    current: Option<T>,
+    /// Widgets that take typed text, where Space types rather than activates.
+    typing: Vec<T>,
+    arrows: bool,
+}
+
+impl<T: Copy + PartialEq> Focus<T> {
+    pub fn new(order: Vec<T>, current: Option<T>) -> Self {
+        Focus {
+            order,
+            current,
+            typing: vec![],
+            arrows: false,
         }
     }
+
+    /// The same, with the arrow keys moving the focus too. Left off where they move the
+    /// caret, so that they don't take the focus off the text.
+    pub fn with_arrows(mut self) -> Self {
+        self.arrows = true;
+        self
+    }
+
+    pub fn with_typing(mut self, typing: Vec<T>) -> Self {
+        self.typing = typing;
+        self
+    }
+
+    pub fn get(&self) -> Option<T> {
+        self.current
+    }
+
+    pub fn is(&self, id: T) -> bool {
+        self.current == Some(id)
+    }
+
+    pub fn set(&mut self, id: Option<T>) {
+        self.current = id;
+    }
+
+    pub fn next(&mut self) {
+        let i = self.position().map_or(0, |i| (i + 1) % self.order.len());
+        self.current = self.order.get(i).copied();
+    }
+
+    pub fn previous(&mut self) {
+        let n = self.order.len();
+        let i = self
+            .position()
+            .map_or(n.saturating_sub(1), |i| (i + n - 1) % n);
+        self.current = self.order.get(i).copied();
+    }
+
+    fn position(&self) -> Option<usize> {
+        self.order.iter().position(|id| Some(*id) == self.current)
+    }
+
+    pub fn handle(&mut self, event: Event, bindings: &Keybindings) -> Nav<T> {
+        let Event::Key(key, mods) = event else {
+            return Nav::Ignored;
+        };
+
+        match (bindings.action_while_typing(key, mods), key) {
+            (Some(Action::FocusNext), _) | (None, KeyCode::Down | KeyCode::Right)
+                if self.arrows =>
+            {
+                self.next();
+                Nav::Moved
+            }
+            (Some(Action::FocusPrevious), _) | (None, KeyCode::Up | KeyCode::Left)
+                if self.arrows =>
+            {
+                self.previous();
+                Nav::Moved
+            }
+            (None, KeyCode::Enter) if self.position().is_some() => {
+                self.current.map_or(Nav::Ignored, Nav::Activated)
+            }
+            (None, KeyCode::Space) if self.position().is_some() => match self.current {
+                Some(id) if !self.typing.contains(&id) => Nav::Activated(id),
+                _ => Nav::Ignored,
+            },
+            _ => Nav::Ignored,
+        }
+    }
+
+    /// Focuses `hovered`, the widget under the mouse, when the mouse moved, or nothing
+    /// when it's over none of them. Returns it when it's clicked.
+    pub fn mouse(&mut self, hovered: Option<T>) -> Option<T> {
+        if input::mouse_delta_position().length() > 0.0 {
+            self.current = hovered;
+        }
+
+        hovered.filter(|_| input::is_mouse_button_pressed(MouseButton::Left))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use macroquad::miniquad::KeyMods;
+
+    #[test]
+    fn navigation() {
+        let bindings = Keybindings::default();
+        let none = KeyMods::default();
+        let key = |key| Event::Key(key, none);
+
+        let mut focus = Focus::new(vec![1, 2, 3], Some(2)).with_typing(vec![2]);
+        assert_eq!(focus.handle(key(KeyCode::Down), &bindings), Nav::Ignored);
+        assert_eq!(focus.handle(key(KeyCode::Space), &bindings), Nav::Ignored);
+
+        let mut focus = Focus::new(vec![1, 2, 3], None)
+            .with_typing(vec![2])
+            .with_arrows();
+        assert_eq!(focus.handle(key(KeyCode::Enter), &bindings), Nav::Ignored);
+
+        assert_eq!(focus.handle(key(KeyCode::Tab), &bindings), Nav::Moved);
+        assert_eq!(focus.get(), Some(1));
+        focus.handle(
+            Event::Key(
+                KeyCode::Tab,
+                KeyMods {
+                    shift: true,
+                    ..none
+                },
+            ),
+            &bindings,
+        );
+        assert_eq!(focus.get(), Some(3));
+        focus.handle(key(KeyCode::Down), &bindings);
+        assert_eq!(focus.get(), Some(1));
+        assert_eq!(
+            focus.handle(key(KeyCode::Space), &bindings),
+            Nav::Activated(1)
+        );
+
+        focus.handle(key(KeyCode::Right), &bindings);
+        assert_eq!(focus.handle(key(KeyCode::Space), &bindings), Nav::Ignored);
+        assert_eq!(
+            focus.handle(key(KeyCode::Enter), &bindings),
+            Nav::Activated(2)
+        );
+        assert_eq!(focus.handle(Event::Char('x'), &bindings), Nav::Ignored);
+
+        // something outside of the order, like the text being typed
+        focus.set(Some(7));
+        assert_eq!(focus.handle(key(KeyCode::Enter), &bindings), Nav::Ignored);
+        focus.previous();
+        assert_eq!(focus.get(), Some(3));
+    }
 }
@@ -0,0 +1,434 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use macroquad::input::KeyCode;
+use macroquad::miniquad::KeyMods;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Restart,
+    Next,
+    Quit,
+    Practice,
+    Theme,
+    FontUp,
+    FontDown,
+    FontReset,
+    FocusNext,
+    FocusPrevious,
+    Settings,
+    Search,
+    Lessons,
+    Session,
+    Palette,
+}
+
+impl Action {
+    pub const ALL: [Action; 15] = [
+        Action::Restart,
+        Action::Next,
+        Action::Quit,
+        Action::Practice,
+        Action::Theme,
+        Action::FontUp,
+        Action::FontDown,
+        Action::FontReset,
+        Action::FocusNext,
+        Action::FocusPrevious,
+        Action::Settings,
+        Action::Search,
+        Action::Lessons,
+        Action::Session,
+        Action::Palette,
+    ];
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Action::Restart => "Restart",
+            Action::Next => "Next",
+            Action::Quit => "Quit",
+            Action::Practice => "Practice",
+            Action::Theme => "Pick a theme",
+            Action::FontUp => "Font size +",
+            Action::FontDown => "Font size -",
+            Action::FontReset => "Reset font size",
+            Action::FocusNext => "Focus next",
+            Action::FocusPrevious => "Focus previous",
+            Action::Settings => "Settings",
+            Action::Search => "Search quotes",
+            Action::Lessons => "Lessons",
+            Action::Session => "Start a session",
+            Action::Palette => "Command palette",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Shortcuts of every action, the `[keybindings]` section of the config. Each action has
+/// a list of key combos like `"ctrl+r"` or `"n"`. Combos without Ctrl, Alt or Super that
+/// type a character only work where there's nothing to type, like the end screen.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Keybindings {
+    pub restart: Vec<KeyCombo>,
+    pub next: Vec<KeyCombo>,
+    pub quit: Vec<KeyCombo>,
+    pub practice: Vec<KeyCombo>,
+    pub theme: Vec<KeyCombo>,
+    pub font_up: Vec<KeyCombo>,
+    pub font_down: Vec<KeyCombo>,
+    pub font_reset: Vec<KeyCombo>,
+    pub focus_next: Vec<KeyCombo>,
+    pub focus_previous: Vec<KeyCombo>,
+    pub settings: Vec<KeyCombo>,
+    pub search: Vec<KeyCombo>,
+    pub lessons: Vec<KeyCombo>,
+    pub session: Vec<KeyCombo>,
+    pub palette: Vec<KeyCombo>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        // window managers on linux tend to keep Super to themselves
+        let m = if cfg!(target_os = "macos") {
+            "super"
+        } else {
+            "ctrl"
+        };
+        let combos = |combos: &[&str]| -> Vec<KeyCombo> {
+            combos
+                .iter()
+                .map(|c| c.replace("mod", m).parse().unwrap())
+                .collect()
+        };
+
+        Keybindings {
+            restart: combos(&["r", "escape", "mod+r"]),
+            next: combos(&["n", "shift+escape", "mod+n"]),
+            quit: combos(&["q", "mod+q"]),
+            practice: combos(&["p"]),
+            theme: combos(&["mod+t"]),
+            font_up: combos(&["mod+="]),
+            font_down: combos(&["mod+-"]),
+            font_reset: combos(&["mod+0"]),
+            focus_next: combos(&["tab"]),
+            focus_previous: combos(&["shift+tab"]),
+            settings: combos(&["mod+,"]),
+            search: vec![],
+            lessons: vec![],
+            session: vec![],
+            palette: combos(&["mod+shift+p"]),
+        }
+    }
+}
+
+impl Keybindings {
+    pub fn action(&self, key: KeyCode, mods: KeyMods) -> Option<Action> {
+        Action::ALL
+            .into_iter()
+            .find(|a| self.combos(*a).iter().any(|c| c.matches(key, mods)))
+    }
+
+    pub fn combos(&self, action: Action) -> &Vec<KeyCombo> {
+        match action {
+            Action::Restart => &self.restart,
+            Action::Next => &self.next,
+            Action::Quit => &self.quit,
+            Action::Practice => &self.practice,
+            Action::Theme => &self.theme,
+            Action::FontUp => &self.font_up,
+            Action::FontDown => &self.font_down,
+            Action::FontReset => &self.font_reset,
+            Action::FocusNext => &self.focus_next,
+            Action::FocusPrevious => &self.focus_previous,
+            Action::Settings => &self.settings,
+            Action::Search => &self.search,
+            Action::Lessons => &self.lessons,
+            Action::Session => &self.session,
+            Action::Palette => &self.palette,
+        }
+    }
+
+    pub fn combos_mut(&mut self, action: Action) -> &mut Vec<KeyCombo> {
+        match action {
+            Action::Restart => &mut self.restart,
+            Action::Next => &mut self.next,
+            Action::Quit => &mut self.quit,
+            Action::Practice => &mut self.practice,
+            Action::Theme => &mut self.theme,
+            Action::FontUp => &mut self.font_up,
+            Action::FontDown => &mut self.font_down,
+            Action::FontReset => &mut self.font_reset,
+            Action::FocusNext => &mut self.focus_next,
+            Action::FocusPrevious => &mut self.focus_previous,
+            Action::Settings => &mut self.settings,
+            Action::Search => &mut self.search,
+            Action::Lessons => &mut self.lessons,
+            Action::Session => &mut self.session,
+            Action::Palette => &mut self.palette,
+        }
+    }
+
+    pub fn action_while_typing(&self, key: KeyCode, mods: KeyMods) -> Option<Action> {
+        let combo = KeyCombo::new(key, mods);
+        (!combo.types()).then(|| self.action(key, mods)).flatten()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyCombo {
+    pub key: KeyCode,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+const KEY_NAMES: [(&str, KeyCode); 33] = [
+    ("tab", KeyCode::Tab),
+    ("enter", KeyCode::Enter),
+    ("escape", KeyCode::Escape),
+    ("space", KeyCode::Space),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("f1", KeyCode::F1),
+    ("f2", KeyCode::F2),
+    ("f3", KeyCode::F3),
+    ("f4", KeyCode::F4),
+    ("f5", KeyCode::F5),
+    ("f6", KeyCode::F6),
+    ("f7", KeyCode::F7),
+    ("f8", KeyCode::F8),
+    ("f9", KeyCode::F9),
+    ("f10", KeyCode::F10),
+    ("f11", KeyCode::F11),
+    ("f12", KeyCode::F12),
+    ("=", KeyCode::Equal),
+    ("-", KeyCode::Minus),
+    (",", KeyCode::Comma),
+    (".", KeyCode::Period),
+    ("/", KeyCode::Slash),
+    (";", KeyCode::Semicolon),
+    ("'", KeyCode::Apostrophe),
+    ("[", KeyCode::LeftBracket),
+    ("]", KeyCode::RightBracket),
+];
+
+const LETTERS: [KeyCode; 26] = [
+    KeyCode::A,
+    KeyCode::B,
+    KeyCode::C,
+    KeyCode::D,
+    KeyCode::E,
+    KeyCode::F,
+    KeyCode::G,
+    KeyCode::H,
+    KeyCode::I,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::M,
+    KeyCode::N,
+    KeyCode::O,
+    KeyCode::P,
+    KeyCode::Q,
+    KeyCode::R,
+    KeyCode::S,
+    KeyCode::T,
+    KeyCode::U,
+    KeyCode::V,
+    KeyCode::W,
+    KeyCode::X,
+    KeyCode::Y,
+    KeyCode::Z,
+];
+
+const DIGITS: [KeyCode; 10] = [
+    KeyCode::Key0,
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+impl KeyCombo {
+    pub fn new(key: KeyCode, mods: KeyMods) -> Self {
+        KeyCombo {
+            key,
+            ctrl: mods.ctrl,
+            shift: mods.shift,
+            alt: mods.alt,
+            logo: mods.logo,
+        }
+    }
+
+    pub fn matches(&self, key: KeyCode, mods: KeyMods) -> bool {
+        *self == KeyCombo::new(key, mods)
+    }
+
+    fn types(&self) -> bool {
+        let printable = LETTERS.contains(&self.key)
+            || DIGITS.contains(&self.key)
+            || self.key == KeyCode::Space
+            || KEY_NAMES
+                .iter()
+                .any(|(n, k)| *k == self.key && n.chars().count() == 1);
+
+        printable && !self.ctrl && !self.alt && !self.logo
+    }
+
+    fn key_name(&self) -> String {
+        if let Some(i) = LETTERS.iter().position(|k| *k == self.key) {
+            return ((b'a' + i as u8) as char).to_string();
+        }
+        if let Some(i) = DIGITS.iter().position(|k| *k == self.key) {
+            return i.to_string();
+        }
+
+        KEY_NAMES.iter().find(|(_, k)| *k == self.key).map_or_else(
+            || format!("{:?}", self.key).to_lowercase(),
+            |(n, _)| n.to_string(),
+        )
+    }
+}
+
+impl FromStr for KeyCombo {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mods = KeyMods::default();
+        let parts: Vec<String> = s.split('+').map(|p| p.trim().to_lowercase()).collect();
+        let (name, modifiers) = parts.split_last().ok_or("empty key combo")?;
+
+        for m in modifiers {
+            match m.as_str() {
+                "ctrl" | "control" => mods.ctrl = true,
+                "shift" => mods.shift = true,
+                "alt" | "option" => mods.alt = true,
+                "super" | "cmd" | "command" | "meta" | "logo" => mods.logo = true,
+                _ => return Err(format!("unknown modifier \"{}\" in \"{}\"", m, s)),
+            }
+        }
+
+        let mut chars = name.chars();
+        let key = match (chars.next(), chars.next()) {
+            (Some(c @ 'a'..='z'), None) => LETTERS[(c as u8 - b'a') as usize],
+            (Some(c @ '0'..='9'), None) => DIGITS[(c as u8 - b'0') as usize],
+            _ => KEY_NAMES
+                .iter()
+                .find(|(n, _)| *n == name || (*n == "escape" && name == "esc"))
+                .map(|(_, k)| *k)
+                .ok_or_else(|| format!("unknown key \"{}\" in \"{}\"", name, s))?,
+        };
+
+        Ok(KeyCombo::new(key, mods))
+    }
+}
+
+impl Display for KeyCombo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (held, name) in [
+            (self.ctrl, "ctrl+"),
+            (self.shift, "shift+"),
+            (self.alt, "alt+"),
+            (self.logo, "super+"),
+        ] {
+            if held {
+                write!(f, "{}", name)?;
+            }
+        }
+
+        write!(f, "{}", self.key_name())
+    }
+}
+
+impl Serialize for KeyCombo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyCombo {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing() {
+        let ctrl = KeyMods {
+            ctrl: true,
+            ..KeyMods::default()
+        };
+
+        assert_eq!("Ctrl+R".parse(), Ok(KeyCombo::new(KeyCode::R, ctrl)));
+        assert_eq!(
+            "esc".parse(),
+            Ok(KeyCombo::new(KeyCode::Escape, KeyMods::default()))
+        );
+        assert!("hyper+r".parse::<KeyCombo>().is_err());
+        assert!("ctrl+".parse::<KeyCombo>().is_err());
+
+        for s in ["ctrl+shift+tab", "super+=", "7", "f5", "alt+;"] {
+            assert_eq!(s.parse::<KeyCombo>().unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn actions() {
+        let bindings = Keybindings {
+            restart: vec!["r".parse().unwrap(), "ctrl+r".parse().unwrap()],
+            ..Keybindings::default()
+        };
+        let none = KeyMods::default();
+        let ctrl = KeyMods { ctrl: true, ..none };
+
+        assert_eq!(bindings.action(KeyCode::R, none), Some(Action::Restart));
+        assert_eq!(bindings.action_while_typing(KeyCode::R, none), None);
+        assert_eq!(
+            bindings.action_while_typing(KeyCode::R, ctrl),
+            Some(Action::Restart)
+        );
+        assert_eq!(
+            bindings.action_while_typing(KeyCode::Tab, none),
+            Some(Action::FocusNext)
+        );
+        assert_eq!(
+            bindings.action(
+                KeyCode::R,
+                KeyMods {
+                    shift: true,
+                    ..none
+                }
+            ),
+            None
+        );
+
+        let s = toml::to_string(&bindings).unwrap();
+        assert_eq!(toml::from_str::<Keybindings>(&s), Ok(bindings));
+        assert_eq!(
+            toml::from_str::<Keybindings>("quit = [\"ctrl+w\"]").map(|b| b.quit),
+            Ok(vec![KeyCombo::new(KeyCode::W, ctrl)])
+        );
+    }
+}
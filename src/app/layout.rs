@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Number of keys in each row of the board, from the top. The number row starts with the
+/// key left of 1 and the second row ends with the key above Enter.
+pub const ROW_LENGTHS: [usize; 4] = [13, 13, 11, 10];
+
+#[derive(PartialEq, Hash, Eq, Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub enum LayoutName {
+    #[default]
+    Qwerty,
+    Dvorak,
+    Colemak,
+    Workman,
+}
+
+/// Which layout to type in, the `[keyboard]` section of the config. Keys are read as if
+/// the system layout were QWERTY and turned into the keys at the same place in this one.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Keyboard {
+    #[serde(default)]
+    pub layout: LayoutName,
+
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+
+    #[serde(default)]
+    pub show: bool,
+}
+
+impl Keyboard {
+    pub fn layout(&self) -> Layout {
+        if let Some(path) = &self.file {
+            match Layout::load(path) {
+                Ok(layout) => return layout,
+                Err(e) => println!("Can't read keyboard layout, using {:?}. {}", self.layout, e),
+            }
+        }
+
+        Layout::builtin(self.layout)
+    }
+}
+
+/// What every key of a board types, row by row from the top left, with and without
+/// shift. Spaces in between are left out, so a layout file can keep its rows apart:
+///
+/// ```toml
+/// keys = "`1234567890-= qwfpgjluy;[]\\ arstdhneio' zxcvbkm,./"
+/// shifted = "~!@#$%^&*()_+ QWFPGJLUY:{}| ARSTDHNEIO\" ZXCVBKM<>?"
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Layout {
+    pub keys: String,
+    pub shifted: String,
+}
+
+impl Layout {
+    pub fn builtin(name: LayoutName) -> Self {
+        let (keys, shifted) = match name {
+            LayoutName::Qwerty => (
+                "`1234567890-= qwertyuiop[]\\ asdfghjkl;' zxcvbnm,./",
+                "~!@#$%^&*()_+ QWERTYUIOP{}| ASDFGHJKL:\" ZXCVBNM<>?",
+            ),
+            LayoutName::Dvorak => (
+                "`1234567890[] ',.pyfgcrl/=\\ aoeuidhtns- ;qjkxbmwvz",
+                "~!@#$%^&*(){} \"<>PYFGCRL?+| AOEUIDHTNS_ :QJKXBMWVZ",
+            ),
+            LayoutName::Colemak => (
+                "`1234567890-= qwfpgjluy;[]\\ arstdhneio' zxcvbkm,./",
+                "~!@#$%^&*()_+ QWFPGJLUY:{}| ARSTDHNEIO\" ZXCVBKM<>?",
+            ),
+            LayoutName::Workman => (
+                "`1234567890-= qdrwbjfup;[]\\ ashtgyneoi' zxmcvkl,./",
+                "~!@#$%^&*()_+ QDRWBJFUP:{}| ASHTGYNEOI\" ZXMCVKL<>?",
+            ),
+        };
+
+        Layout {
+            keys: keys.replace(' ', ""),
+            shifted: shifted.replace(' ', ""),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let s = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let layout: Layout =
+            toml::from_str(&s).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        Layout::new(&layout.keys, &layout.shifted).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn new(keys: &str, shifted: &str) -> Result<Self, String> {
+        let total: usize = ROW_LENGTHS.iter().sum();
+        let keys: String = keys.chars().filter(|c| !c.is_whitespace()).collect();
+        let shifted: String = shifted.chars().filter(|c| !c.is_whitespace()).collect();
+
+        for (name, s) in [("keys", &keys), ("shifted", &shifted)] {
+            let n = s.chars().count();
+            if n != total {
+                return Err(format!(
+                    "{} has {} characters instead of {}",
+                    name, n, total
+                ));
+            }
+        }
+
+        Ok(Layout { keys, shifted })
+    }
+
+    pub fn rows(&self) -> Vec<Vec<char>> {
+        split_rows(&self.keys)
+    }
+
+    pub fn shifted_rows(&self) -> Vec<Vec<char>> {
+        split_rows(&self.shifted)
+    }
+
+    pub fn remap(&self) -> HashMap<char, char> {
+        let qwerty = Layout::builtin(LayoutName::Qwerty);
+
+        qwerty
+            .keys
+            .chars()
+            .zip(self.keys.chars())
+            .chain(qwerty.shifted.chars().zip(self.shifted.chars()))
+            .filter(|(from, to)| from != to)
+            .collect()
+    }
+}
+
+fn split_rows(keys: &str) -> Vec<Vec<char>> {
+    let mut keys = keys.chars();
+    ROW_LENGTHS
+        .iter()
+        .map(|&n| keys.by_ref().take(n).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin() {
+        for name in [
+            LayoutName::Qwerty,
+            LayoutName::Dvorak,
+            LayoutName::Colemak,
+            LayoutName::Workman,
+        ] {
+            let layout = Layout::builtin(name);
+            assert_eq!(Layout::new(&layout.keys, &layout.shifted), Ok(layout));
+        }
+
+        assert!(Layout::builtin(LayoutName::Qwerty).remap().is_empty());
+
+        let dvorak = Layout::builtin(LayoutName::Dvorak).remap();
+        assert_eq!(dvorak.get(&'s'), Some(&'o'));
+        assert_eq!(dvorak.get(&'S'), Some(&'O'));
+        assert_eq!(dvorak.get(&'q'), Some(&'\''));
+        assert_eq!(dvorak.get(&'a'), None);
+
+        assert_eq!(
+            Layout::builtin(LayoutName::Workman).rows()[2],
+            "ashtgyneoi'".chars().collect::<Vec<char>>()
+        );
+    }
+
+    #[test]
+    fn user_layout() {
+        let colemak = Layout::builtin(LayoutName::Colemak);
+        let s = toml::to_string(&Layout {
+            keys: "`1234567890-=\nqwfpgjluy;[]\\\narstdhneio'\nzxcvbkm,./".to_string(),
+            shifted: colemak.shifted.clone(),
+        })
+        .unwrap();
+        let path =
+            std::env::temp_dir().join(format!("typing_test_layout_{}.toml", std::process::id()));
+        fs::write(&path, s).unwrap();
+
+        assert_eq!(Layout::load(&path), Ok(colemak));
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(
+            Layout::new("abc", "ABC"),
+            Err("keys has 3 characters instead of 47".to_string())
+        );
+    }
+}
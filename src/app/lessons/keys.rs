@@ -0,0 +1,123 @@
+use std::rc::Rc;
+
+use macroquad::color::Color;
+use macroquad::text::{self, Font};
+use macroquad::window;
+
+use crate::app::text::PrintOptions;
+use crate::app::theme::Theme;
+use crate::app::{self, Style, Value};
+use crate::data_provider::{Lessons, FIRST_KEYS, KEY_ORDER};
+
+pub struct Keys {
+    pub style: Style,
+    font: Rc<Font>,
+}
+
+impl Keys {
+    pub fn new(style: &Style, font: Rc<Font>) -> Self {
+        let f1 = Rc::clone(&style.font_size);
+
+        Keys {
+            font,
+            style: Style {
+                x: Value::Relative(Box::new(|this| {
+                    (window::screen_width() - this.width()) / 2.0
+                })),
+                y: Value::Relative(Box::new(|_| window::screen_height() * 0.2)),
+                width: Value::Relative(Box::new(move |_| {
+                    KEY_ORDER.len() as f32 * *f1.borrow() * 1.2
+                })),
+                font_size: Rc::clone(&style.font_size),
+                theme: Theme {
+                    bg: Rc::clone(&style.theme.bg),
+                    ghost: Rc::clone(&style.theme.ghost),
+                    text: Rc::clone(&style.theme.text),
+                    error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
+                },
+                ..Style::default()
+            },
+        }
+    }
+
+    fn key_width(&self) -> f32 {
+        *self.style.font_size.borrow() * 1.2
+    }
+
+    pub fn hovered(&self) -> Option<usize> {
+        let (x, y) = macroquad::input::mouse_position();
+        let fsize = *self.style.font_size.borrow();
+
+        if y < self.style.y() || y > self.style.y() + 1.5 * fsize || x < self.style.x() {
+            return None;
+        }
+
+        let i = ((x - self.style.x()) / self.key_width()) as usize;
+        (i < KEY_ORDER.len()).then_some(i)
+    }
+
+    pub fn update(&self, lessons: &Lessons, stage: usize) {
+        let fsize = *self.style.font_size.borrow();
+        let locked = Color {
+            a: 0.4,
+            ..*self.style.theme.ghost.borrow()
+        };
+
+        for (i, key) in KEY_ORDER.chars().enumerate() {
+            let color = if i < stage {
+                *self.style.theme.text.borrow()
+            } else if i < lessons.unlocked {
+                *self.style.theme.ghost.borrow()
+            } else if i == lessons.unlocked {
+                *self.style.theme.error.borrow()
+            } else {
+                locked
+            };
+
+            let s = key.to_string();
+            let width = text::measure_text(&s, Some(&self.font), fsize as u16, 1.0).width;
+
+            app::text::print_text(
+                &self.style,
+                &s,
+                PrintOptions {
+                    x: Some(
+                        self.style.x()
+                            + i as f32 * self.key_width()
+                            + (self.key_width() - width) / 2.0,
+                    ),
+                    font: Some(Rc::clone(&self.font)),
+                    color: Some(color),
+                    ..PrintOptions::default()
+                },
+            );
+        }
+
+        let goal = match lessons.next_key() {
+            Some(k) => format!(
+                "Lesson {} of {}. Reach {} wpm at {}% accuracy to unlock {}",
+                lessons.unlocked + 1 - FIRST_KEYS,
+                KEY_ORDER.len() + 1 - FIRST_KEYS,
+                lessons.target_wpm,
+                lessons.target_accuracy,
+                k
+            ),
+            None => "Every key is unlocked".to_string(),
+        };
+        let width = text::measure_text(&goal, Some(&self.font), (fsize * 0.7) as u16, 1.0).width;
+
+        app::text::print_text(
+            &self.style,
+            &goal,
+            PrintOptions {
+                x: Some((window::screen_width() - width) / 2.0),
+                y: Some(self.style.y() + 2.5 * fsize),
+                font: Some(Rc::clone(&self.font)),
+                font_size: Some(fsize * 0.7),
+                color: Some(*self.style.theme.ghost.borrow()),
+                ..PrintOptions::default()
+            },
+        );
+    }
+}
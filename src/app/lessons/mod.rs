@@ -0,0 +1,143 @@
+use std::rc::Rc;
+
+use macroquad::input::{self, KeyCode, MouseButton};
+use macroquad::window;
+
+use super::events::Event;
+use super::focus::{Focus, LessonsFocus, Nav};
+use super::widget::Widget;
+use super::{App, Mode, Screen};
+use crate::data_provider::{Lessons, FIRST_KEYS};
+
+mod keys;
+
+pub async fn run(app: &mut App) {
+    input::show_mouse(true);
+    input::clear_input_queue();
+    app.events.drain();
+
+    let mut focus = Focus::new(
+        vec![
+            LessonsFocus::Start,
+            LessonsFocus::Reset,
+            LessonsFocus::Cancel,
+        ],
+        None,
+    )
+    .with_arrows();
+
+    // how many keys the lesson to start uses, the newest lesson unless an earlier one is
+    // picked with the arrows or the mouse
+    let mut stage = app.config.lesson.keys().len();
+
+    let keys = keys::Keys::new(&app.style, Rc::clone(&app.font));
+    let button = |id, text| Widget::button(id, text, &app.style, Rc::clone(&app.font));
+    let mut buttons = Widget::column(
+        &app.style,
+        1.0,
+        vec![
+            button(LessonsFocus::Start, "Start"),
+            button(LessonsFocus::Reset, "Reset progress"),
+        ],
+    );
+    let mut cancel_button = button(LessonsFocus::Cancel, "Cancel (ESC)");
+
+    loop {
+        let fsize = *app.style.font_size.borrow();
+        buttons.center(window::screen_height() / 2.0);
+        cancel_button.center(window::screen_height() - 2.0 * fsize - 30.0);
+
+        let events = app.read_events();
+        if app.state.screen != Screen::Lessons {
+            return;
+        }
+        for (event, _) in events {
+            // the arrows pick the lesson rather than move the focus sideways
+            match event {
+                Event::Key(KeyCode::Left, _) => {
+                    stage = (stage - 1).max(FIRST_KEYS);
+                    continue;
+                }
+                Event::Key(KeyCode::Right, _) => {
+                    stage = (stage + 1).min(app.config.lesson.keys().len());
+                    continue;
+                }
+                _ => (),
+            }
+
+            match focus.handle(event, &app.config.keybindings) {
+                Nav::Moved => continue,
+                Nav::Activated(id) => {
+                    if press(app, id, &mut stage) {
+                        return;
+                    }
+                    continue;
+                }
+                Nav::Ignored => (),
+            }
+
+            match event {
+                Event::Key(KeyCode::Escape, _) => {
+                    app.state.screen = Screen::TypingTest;
+                    return;
+                }
+                Event::Key(KeyCode::Enter, _) => {
+                    start(app, stage);
+                    return;
+                }
+                Event::Key(key, mods) => {
+                    if let Some(action) = app.config.keybindings.action(key, mods) {
+                        app.font_action(action);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let hovered = buttons.hovered().or_else(|| cancel_button.hovered());
+        if let Some(id) = focus.mouse(hovered) {
+            if press(app, id, &mut stage) {
+                return;
+            }
+        } else if input::is_mouse_button_pressed(MouseButton::Left) {
+            if let Some(i) = keys.hovered() {
+                if i < app.config.lesson.keys().len() {
+                    stage = (i + 1).max(FIRST_KEYS);
+                }
+            }
+        }
+
+        window::clear_background(*app.style.theme.bg.borrow());
+
+        keys.update(&app.config.lesson, stage);
+        buttons.draw(focus.get());
+        cancel_button.draw(focus.get());
+        app.palette.update();
+
+        window::next_frame().await;
+    }
+}
+
+fn press(app: &mut App, id: LessonsFocus, stage: &mut usize) -> bool {
+    match id {
+        LessonsFocus::Start => start(app, *stage),
+        LessonsFocus::Reset => {
+            app.config.lesson.unlocked = Lessons::default().unlocked;
+            app.config.update_file();
+            *stage = app.config.lesson.keys().len();
+            return false;
+        }
+        LessonsFocus::Cancel => app.state.screen = Screen::TypingTest,
+    }
+
+    true
+}
+
+fn start(app: &mut App, stage: usize) {
+    app.state.mode = Mode::Lesson {
+        keys: app.config.lesson.keys()[..stage].to_string(),
+        s: "".to_string(),
+    };
+    app.next_text();
+    app.state.screen = Screen::TypingTest;
+}
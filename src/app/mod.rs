@@ -1,14 +1,17 @@
-use crate::data_provider::{Data, Quote};
-use crate::Config;
+use crate::data_provider::{self, DataProvider, Quote};
+use crate::{Config, RunResult};
 use macroquad::color::Color;
 use macroquad::text::{load_ttf_font_from_bytes, Font};
 use macroquad::window;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::error::Error;
 use std::fmt::Display;
+use std::process;
 use std::rc::Rc;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 mod theme;
 
 mod style;
@@ -17,64 +20,349 @@ pub use style::{BorderParams, Style, Value};
 use self::theme::Theme;
 pub use self::theme::ThemeName;
 
+pub use self::caret::{Caret, CaretStyle};
+pub use self::keybindings::{Action, KeyCombo, Keybindings};
+pub use self::layout::{Keyboard, Layout, LayoutName};
+
+use self::events::Event;
+use self::palette::Command;
+
 mod text;
 mod util;
+mod widget;
 
+mod caret;
 mod endscreen;
+mod events;
 mod focus;
+mod keybindings;
+mod layout;
+mod lessons;
+mod palette;
+mod quote_search;
+mod rest;
+mod settings;
 mod theme_select;
 mod typing_test;
 
 pub struct App {
     style: Style,
-    data: Data,
+    data: Box<dyn DataProvider>,
     config: Config,
     state: AppState,
+    events: events::Events,
+    palette: palette::Palette,
+    ngrams: Vec<String>,
     pub typing_font: Rc<Font>,
     pub font: Rc<Font>,
 }
 
 impl App {
-    pub async fn new(data: Data, config: Config) -> Self {
+    pub async fn new(data: Box<dyn DataProvider>, config: Config) -> Self {
         let typing_font: Font = load_ttf_font_from_bytes(include_bytes!(
             "../data/SauceCodeProNerdFontMono-Regular.ttf"
         ))
         .unwrap();
 
         let font: Font = load_ttf_font_from_bytes(include_bytes!("../data/Helvetica.ttc")).unwrap();
+        let font = Rc::new(font);
+
+        let style = Style {
+            font_size: Rc::new(RefCell::new(config.font_size)),
+            width: Value::Relative(Box::new(|_| window::screen_width())),
+            height: Value::Relative(Box::new(|_| window::screen_height())),
+            theme: Theme::get_theme(&config.theme),
+            ..Style::default()
+        };
 
         App {
             data,
-            style: Style {
-                font_size: Rc::new(RefCell::new(config.font_size)),
-                width: Value::Relative(Box::new(|_| window::screen_width())),
-                height: Value::Relative(Box::new(|_| window::screen_height())),
-                theme: Theme::get_theme(&config.theme),
-                ..Style::default()
-            },
+            palette: palette::Palette::new(&style, Rc::clone(&font)),
+            style,
             state: AppState {
                 mode: config.mode.clone(),
+                rng: config
+                    .seed
+                    .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64),
                 ..AppState::default()
             },
+            events: events::Events::new(&config.keyboard.layout()),
+            ngrams: vec![],
             config,
             typing_font: Rc::new(typing_font),
-            font: Rc::new(font),
+            font,
         }
     }
 
     pub async fn main_loop(&mut self) -> Result<(), Box<dyn Error>> {
-        self.state.mode.next(&self.data);
+        self.ngrams = self.config.drill.load()?;
+
+        // the config remembers the last text, only pick a new one if there is none, a
+        // seed was given or the daily challenge may have changed since
+        if self.config.start_session {
+            self.start_session();
+        } else if self.state.mode.get_inner().is_empty()
+            || self.config.seed.is_some()
+            || matches!(self.state.mode, Mode::Daily { .. })
+        {
+            self.next_text();
+        }
 
         loop {
             match self.state.screen {
                 Screen::TypingTest => typing_test::run(self).await,
                 Screen::End => endscreen::run(self).await,
                 Screen::ThemeSelect => theme_select::run(self).await,
+                Screen::QuoteSearch => quote_search::run(self).await,
+                Screen::Lessons => lessons::run(self).await,
+                Screen::Rest => rest::run(self).await,
+                Screen::Settings => settings::run(self).await,
             };
         }
     }
 }
 
+impl App {
+    /// Picks a new text for the current mode and saves it to the config so that
+    /// reopening the app brings back the same test.
+    fn next_text(&mut self) {
+        let seed = match self.state.mode {
+            Mode::Daily { .. } => Mode::today(),
+            _ => self
+                .config
+                .seed
+                .take()
+                .unwrap_or_else(|| self.state.rng.gen::<u32>() as u64),
+        };
+
+        self.state.seed = Some(seed);
+        self.state
+            .mode
+            .next(self.data.as_ref(), &mut StdRng::seed_from_u64(seed));
+        self.save_mode();
+    }
+
+    fn save_mode(&mut self) {
+        self.config.mode = self.state.mode.clone();
+        self.config.update_file();
+    }
+
+    fn finish(&mut self) -> Option<String> {
+        let (wpm, accuracy) = (self.state.wpm, self.state.accuracy);
+        // a failed test never moves a lesson or a drill on
+        let failed = self
+            .state
+            .history
+            .last()
+            .is_some_and(|r| r.failed.is_some());
+
+        match &self.state.mode {
+            Mode::Lesson { keys, .. } => {
+                let lessons = &mut self.config.lesson;
+
+                match lessons.next_key() {
+                    None => Some("every key is unlocked".to_string()),
+                    // an earlier lesson picked from the lessons screen
+                    Some(_) if keys.len() < lessons.keys().len() => None,
+                    Some(k) if !failed && lessons.passed(wpm, accuracy) => {
+                        lessons.unlocked += 1;
+                        self.state.mode = Mode::Lesson {
+                            keys: lessons.keys().to_string(),
+                            s: String::new(),
+                        };
+                        // a restart types the new key too
+                        self.next_text();
+
+                        Some(format!("unlocked {}", k))
+                    }
+                    Some(k) => Some(format!(
+                        "reach {} wpm at {}% accuracy to unlock {}",
+                        lessons.target_wpm, lessons.target_accuracy, k
+                    )),
+                }
+            }
+            Mode::Drill { .. } => {
+                let drill = &mut self.config.drill;
+
+                if !failed && drill.passed(wpm, accuracy) {
+                    drill.position += 1;
+                    self.state.mode = self.drill_mode();
+                    // the line of the last n-gram would skip this one
+                    self.next_text();
+
+                    Some(format!("next up {}", self.state.mode))
+                } else {
+                    Some(format!(
+                        "reach {} wpm at {}% accuracy to move on",
+                        drill.target_wpm, drill.target_accuracy
+                    ))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn start_session(&mut self) {
+        for name in &self.config.session.modes {
+            if self.session_mode(name).is_none() {
+                println!("Unknown session mode \"{}\", using the current mode.", name);
+            }
+        }
+
+        self.state.session = Some(SessionState {
+            started: Instant::now(),
+            runs: vec![],
+        });
+        self.next_session_test();
+    }
+
+    fn next_session_test(&mut self) {
+        let modes = &self.config.session.modes;
+        let done = self.session_runs().map_or(0, |r| r.len());
+
+        if !modes.is_empty() {
+            if let Some(mode) = self.session_mode(&modes[done % modes.len()]) {
+                self.state.mode = mode;
+            }
+        }
+        self.next_text();
+    }
+
+    fn session_runs(&self) -> Option<&[RunResult]> {
+        self.state.session.as_ref().map(|s| s.runs.as_slice())
+    }
+
+    fn session_over(&self) -> bool {
+        let (Some(session), Some(runs)) = (&self.state.session, self.session_runs()) else {
+            return false;
+        };
+
+        match self.config.session.minutes {
+            Some(minutes) => session.started.elapsed() >= Duration::from_secs(minutes * 60),
+            None => runs.len() >= self.config.session.tests,
+        }
+    }
+
+    fn session_mode(&self, name: &str) -> Option<Mode> {
+        let mut parts = name.split_whitespace();
+        let s = "".to_string();
+
+        match (parts.next()?.to_lowercase().as_str(), parts.next()) {
+            ("words", n) => Some(Mode::Words {
+                n: n.and_then(|n| n.parse().ok()).unwrap_or(25),
+                s,
+            }),
+            ("quote", None) => Some(Mode::default()),
+            ("daily", None) => Some(Mode::Daily { day: 0, s }),
+            ("zen", None) => Some(Mode::Zen { s }),
+            ("drill", None) => Some(self.drill_mode()),
+            ("lesson", None) => Some(Mode::Lesson {
+                keys: self.config.lesson.keys().to_string(),
+                s,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The key and character events of this frame for the screen to handle. While the
+    /// command palette is open it takes them instead, and a picked command that belongs
+    /// to the screen comes back as an `Event::Action`.
+    fn read_events(&mut self) -> Vec<(Event, Instant)> {
+        let mut events = vec![];
+
+        for (event, at) in self.events.drain() {
+            let toggle = match event {
+                Event::Key(key, mods) => {
+                    self.config.keybindings.action_while_typing(key, mods) == Some(Action::Palette)
+                }
+                _ => false,
+            };
+
+            if toggle {
+                self.palette.toggle();
+            } else if !self.palette.open {
+                events.push((event, at));
+                continue;
+            } else if let Some(command) = self.palette.input(event) {
+                events.extend(self.run_command(command).map(|e| (e, at)));
+            }
+
+            let commands = palette::commands(self);
+            self.palette.filter(commands);
+        }
+
+        events
+    }
+
+    fn run_command(&mut self, command: Command) -> Option<Event> {
+        match command {
+            Command::Action(action @ (Action::FontUp | Action::FontDown | Action::FontReset)) => {
+                self.font_action(action);
+                None
+            }
+            Command::Action(Action::Quit) => process::exit(0),
+            Command::Action(action) => Some(Event::Action(action)),
+            Command::Theme(theme) => {
+                self.style.theme.set(&theme);
+                self.config.theme = theme;
+                self.config.update_file();
+                None
+            }
+            // the new text is typed on the typing test, which restarts to put it in the
+            // typing box while the other screens leave for it
+            Command::Mode(mode) => {
+                self.set_mode(&mode);
+                self.state.screen = Screen::TypingTest;
+                Some(Event::Action(Action::Restart))
+            }
+            Command::Quote(id) => {
+                self.load_quote(id);
+                self.state.screen = Screen::TypingTest;
+                Some(Event::Action(Action::Restart))
+            }
+        }
+    }
+
+    fn set_mode(&mut self, mode: &Mode) {
+        self.state.mode = match mode {
+            Mode::Drill { .. } => self.drill_mode(),
+            Mode::Lesson { .. } => Mode::Lesson {
+                keys: self.config.lesson.keys().to_string(),
+                s: "".to_string(),
+            },
+            mode => mode.clone(),
+        };
+        self.next_text();
+    }
+
+    fn load_quote(&mut self, id: usize) {
+        self.state.mode = Mode::Quote(self.data.quotes()[id].clone());
+        self.state.seed = None;
+        self.save_mode();
+    }
+
+    fn font_action(&self, action: Action) {
+        let mut size = self.style.font_size.borrow_mut();
+
+        match action {
+            Action::FontUp => *size += 5.0,
+            Action::FontDown => *size -= 5.0,
+            Action::FontReset => *size = self.config.font_size,
+            _ => (),
+        }
+    }
+
+    fn drill_mode(&self) -> Mode {
+        let drill = &self.config.drill;
+
+        Mode::Drill {
+            ngram: drill.current(&self.ngrams),
+            repetitions: drill.repetitions,
+            s: String::new(),
+        }
+    }
+}
+
 pub struct AppState {
     wpm: f32,
     mode: Mode,
@@ -83,6 +371,24 @@ pub struct AppState {
     max_wpm: f32,
     time: Duration,
     accuracy: i32,
+    rng: StdRng,
+    seed: Option<u64>,
+    missed: Vec<String>,
+    history: Vec<RunResult>,
+    session: Option<SessionState>,
+    sprints: Vec<Sprint>,
+}
+
+struct Sprint {
+    samples: Vec<(Duration, f32)>,
+    wpm: f32,
+    accuracy: i32,
+}
+
+struct SessionState {
+    started: Instant,
+    /// A test typed again from the end screen only keeps its last result.
+    runs: Vec<RunResult>,
 }
 
 impl AppState {
@@ -107,6 +413,12 @@ impl Default for AppState {
             max_wpm: 0.0,
             time: Duration::from_secs(0),
             accuracy: 0,
+            rng: StdRng::from_entropy(),
+            seed: None,
+            missed: vec![],
+            history: vec![],
+            session: None,
+            sprints: vec![],
         }
     }
 }
@@ -116,6 +428,10 @@ enum Screen {
     TypingTest,
     End,
     ThemeSelect,
+    QuoteSearch,
+    Lessons,
+    Rest,
+    Settings,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -123,63 +439,191 @@ pub enum Mode {
     Words {
         n: usize,
 
-        #[serde(skip)]
+        #[serde(default)]
         s: String,
     },
     Quote(Quote),
+    Daily {
+        #[serde(default)]
+        day: u64,
+
+        #[serde(default)]
+        s: String,
+    },
+    Zen {
+        #[serde(default)]
+        s: String,
+    },
+    Missed {
+        words: Vec<String>,
+        repeat: usize,
+
+        #[serde(default)]
+        s: String,
+    },
+    Drill {
+        ngram: String,
+        repetitions: usize,
+
+        #[serde(default)]
+        s: String,
+    },
+    Lesson {
+        keys: String,
+
+        #[serde(default)]
+        s: String,
+    },
+    Sprint {
+        #[serde(default)]
+        s: String,
+    },
 }
 
+pub(crate) const LINE_LENGTH: usize = 80;
+
+const DAILY_WORDS: usize = 50;
+
+const LESSON_WORDS: usize = 25;
+
+/// Number of words in a sprint, more than anyone types before the time is up.
+const SPRINT_WORDS: usize = 200;
+
 impl Mode {
-    pub fn new(data: &Data) -> Self {
-        // Self::with_quote(data)
-        Self::with_words(data, 10)
+    pub fn new(data: &dyn DataProvider, rng: &mut dyn RngCore) -> Self {
+        // Self::with_quote(data, rng)
+        Self::with_words(data, 10, rng)
     }
 
-    pub fn with_quote(data: &Data) -> Self {
-        Mode::Quote(data.get_random_quote().clone())
+    pub fn with_quote(data: &dyn DataProvider, rng: &mut dyn RngCore) -> Self {
+        data.next_text(&Mode::default(), rng)
     }
 
-    pub fn with_words(data: &Data, n: usize) -> Self {
-        let words = data.get_n_random_words(n);
-        Mode::Words {
-            n,
-            s: words
-                .iter()
-                .map(|w| w.to_string())
-                .collect::<Vec<String>>()
-                .join(" "),
-        }
+    pub fn with_words(data: &dyn DataProvider, n: usize, rng: &mut dyn RngCore) -> Self {
+        data.next_text(
+            &Mode::Words {
+                n,
+                s: "".to_string(),
+            },
+            rng,
+        )
     }
 
     pub fn get_inner(&self) -> String {
         match self {
             Mode::Words { s, .. } => s.to_string(),
             Mode::Quote(q) => q.quote.clone(),
+            Mode::Daily { s, .. } => s.to_string(),
+            Mode::Zen { s } => s.to_string(),
+            Mode::Missed { s, .. } => s.to_string(),
+            Mode::Drill { s, .. } => s.to_string(),
+            Mode::Lesson { s, .. } => s.to_string(),
+            Mode::Sprint { s } => s.to_string(),
         }
     }
 
-    pub fn next(&mut self, data: &Data) {
-        let new_mode = match self {
-            Mode::Words { n, .. } => Mode::Words {
-                s: data
-                    .get_n_random_words(*n)
-                    .iter()
-                    .map(|s| &(*s)[..])
-                    .collect::<Vec<&str>>()
-                    .join(" "),
-                n: *n,
+    pub fn next(&mut self, data: &dyn DataProvider, rng: &mut dyn RngCore) {
+        *self = match self {
+            // zen, practice, drills and lessons don't depend on the words of the provider
+            Mode::Zen { .. } => Mode::Zen { s: "".to_string() },
+            Mode::Missed { words, repeat, .. } => Mode::Missed {
+                s: data_provider::practice_text(words, *repeat, rng),
+                words: words.clone(),
+                repeat: *repeat,
+            },
+            Mode::Drill {
+                ngram, repetitions, ..
+            } => Mode::Drill {
+                s: data_provider::drill_line(ngram, *repetitions),
+                ngram: ngram.clone(),
+                repetitions: *repetitions,
+            },
+            Mode::Lesson { keys, .. } => Mode::Lesson {
+                s: data_provider::pseudo_words(keys, LESSON_WORDS, rng),
+                keys: keys.clone(),
             },
-            Mode::Quote(_) => Mode::Quote(data.get_random_quote().clone()),
+            _ => data.next_text(self, rng),
         };
-        *self = new_mode;
+    }
+
+    pub fn word_count(&self) -> Option<usize> {
+        match self {
+            Mode::Words { n, .. } => Some(*n),
+            Mode::Daily { .. } => Some(DAILY_WORDS),
+            Mode::Zen { .. } => None,
+            Mode::Missed { words, repeat, .. } => Some(words.len() * repeat),
+            Mode::Drill { repetitions, .. } => Some(*repetitions),
+            Mode::Lesson { .. } => Some(LESSON_WORDS),
+            Mode::Sprint { .. } => Some(SPRINT_WORDS),
+            Mode::Quote(_) => None,
+        }
+    }
+
+    pub fn with_text(&self, s: String) -> Mode {
+        match self {
+            Mode::Words { n, .. } => Mode::Words { n: *n, s },
+            Mode::Daily { .. } => Mode::Daily {
+                day: Mode::today(),
+                s,
+            },
+            Mode::Zen { .. } => Mode::Zen { s },
+            Mode::Missed { words, repeat, .. } => Mode::Missed {
+                words: words.clone(),
+                repeat: *repeat,
+                s,
+            },
+            Mode::Drill {
+                ngram, repetitions, ..
+            } => Mode::Drill {
+                ngram: ngram.clone(),
+                repetitions: *repetitions,
+                s,
+            },
+            Mode::Lesson { keys, .. } => Mode::Lesson {
+                keys: keys.clone(),
+                s,
+            },
+            Mode::Sprint { .. } => Mode::Sprint { s },
+            Mode::Quote(q) => Mode::Quote(Quote {
+                source: q.source.clone(),
+                quote: s,
+            }),
+        }
     }
 
     pub fn get_name(&self) -> String {
         match self {
             Mode::Quote(_) => "Quote".to_string(),
             Mode::Words { .. } => "Words".to_string(),
+            Mode::Daily { .. } => "Daily".to_string(),
+            Mode::Zen { .. } => "Zen".to_string(),
+            Mode::Missed { .. } => "Missed".to_string(),
+            Mode::Drill { .. } => "Drill".to_string(),
+            Mode::Lesson { .. } => "Lesson".to_string(),
+            Mode::Sprint { .. } => "Sprint".to_string(),
         }
     }
+
+    /// Days since the unix epoch in UTC, the seed of the daily challenge.
+    pub fn today() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() / (24 * 60 * 60))
+    }
+}
+
+fn civil_from_days(days: u64) -> (i64, u32, u32) {
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+
+    (y, m, d)
 }
 
 impl Default for Mode {
@@ -196,6 +640,20 @@ impl Display for Mode {
         match self {
             Mode::Words { n, .. } => write!(f, "{} {}", n, if *n != 1 { "Words" } else { "Word" }),
             Mode::Quote(Quote { source, .. }) => write!(f, "{}", source),
+            Mode::Daily { day, .. } => {
+                let (y, m, d) = civil_from_days(*day);
+                write!(f, "Daily {}-{:02}-{:02}", y, m, d)
+            }
+            Mode::Zen { .. } => write!(f, "Zen"),
+            Mode::Missed { words, .. } => write!(
+                f,
+                "Practice {} {}",
+                words.len(),
+                if words.len() != 1 { "Words" } else { "Word" }
+            ),
+            Mode::Drill { ngram, .. } => write!(f, "Drill {}", ngram),
+            Mode::Lesson { keys, .. } => write!(f, "Lesson {}", keys),
+            Mode::Sprint { .. } => write!(f, "Sprint"),
         }
     }
 }
@@ -208,6 +666,12 @@ impl PartialEq for Mode {
                 _ => false,
             },
             Mode::Quote(_) => matches!(other, Mode::Quote(_)),
+            Mode::Daily { .. } => matches!(other, Mode::Daily { .. }),
+            Mode::Zen { .. } => matches!(other, Mode::Zen { .. }),
+            Mode::Missed { .. } => matches!(other, Mode::Missed { .. }),
+            Mode::Drill { .. } => matches!(other, Mode::Drill { .. }),
+            Mode::Lesson { .. } => matches!(other, Mode::Lesson { .. }),
+            Mode::Sprint { .. } => matches!(other, Mode::Sprint { .. }),
         }
     }
 }
@@ -219,6 +683,9 @@ pub struct Word {
     pub id: usize,
     pub word: String,
     pub last_typed: usize,
+    pub typed: String,
+    /// How long it took to type the word, counted from when the one before was done.
+    pub elapsed: Duration,
 }
 
 impl Word {
@@ -238,6 +705,8 @@ impl Word {
             id,
             word: text.to_string(),
             last_typed: 0,
+            typed: String::new(),
+            elapsed: Duration::ZERO,
         }
     }
 }
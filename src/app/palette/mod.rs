@@ -0,0 +1,317 @@
+use std::rc::Rc;
+
+use macroquad::color::Color;
+use macroquad::input::KeyCode;
+use macroquad::text::Font;
+use macroquad::{shapes, window};
+
+use super::events::Event;
+use super::text::PrintOptions;
+use super::theme::{Theme, ThemeName};
+use super::{Action, App, Mode, Screen, Style, Value};
+
+const SHOWN: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Action(Action),
+    Theme(ThemeName),
+    Mode(Mode),
+    Quote(usize),
+}
+
+impl Command {
+    fn label(&self, app: &App) -> String {
+        match self {
+            Command::Action(action) => action.to_string(),
+            Command::Theme(theme) => format!("Change theme: {:?}", theme),
+            Command::Mode(Mode::Words { n, .. }) => format!("Mode: Words {}", n),
+            Command::Mode(mode) => format!("Mode: {}", mode.get_name()),
+            Command::Quote(id) => format!("Load quote #{}: {}", id, app.data.quotes()[*id].source),
+        }
+    }
+}
+
+pub fn commands(app: &App) -> Vec<(String, Command)> {
+    let mut commands = vec![];
+
+    // "quote 12" or "#12" loads a quote by its index
+    let query = app.palette.query.trim().to_lowercase();
+    let id = query
+        .strip_prefix('#')
+        .or_else(|| query.strip_prefix("quote"))
+        .and_then(|id| id.trim().parse::<usize>().ok())
+        .filter(|id| *id < app.data.quotes().len());
+    commands.extend(id.map(Command::Quote));
+
+    let on_test = matches!(app.state.screen, Screen::TypingTest | Screen::End);
+    commands.extend(
+        Action::ALL
+            .into_iter()
+            .filter(|action| match action {
+                Action::FontUp | Action::FontDown | Action::FontReset | Action::Quit => true,
+                Action::Practice => app.state.screen == Screen::End && !app.state.missed.is_empty(),
+                Action::FocusNext | Action::FocusPrevious | Action::Palette => false,
+                _ => on_test,
+            })
+            .map(Command::Action),
+    );
+
+    if app.state.screen != Screen::ThemeSelect {
+        commands.extend(
+            [
+                ThemeName::Atom,
+                ThemeName::Catppuccin,
+                ThemeName::Gruvbox,
+                ThemeName::Tokyonight,
+            ]
+            .map(Command::Theme),
+        );
+    }
+
+    let s = String::new();
+    commands.extend(
+        [10, 25, 50, 100]
+            .map(|n| Mode::Words { n, s: s.clone() })
+            .into_iter()
+            .chain([
+                Mode::default(),
+                Mode::Daily {
+                    day: 0,
+                    s: s.clone(),
+                },
+                Mode::Zen { s: s.clone() },
+                Mode::Drill {
+                    ngram: s.clone(),
+                    repetitions: 0,
+                    s: s.clone(),
+                },
+                Mode::Lesson {
+                    keys: s.clone(),
+                    s: s.clone(),
+                },
+                Mode::Sprint { s },
+            ])
+            .map(Command::Mode),
+    );
+
+    commands
+        .into_iter()
+        .map(|command| (command.label(app), command))
+        .collect()
+}
+
+/// How well `query` matches `label`, `None` when its characters aren't all in it in
+/// order. Characters next to each other and at the start of words count for more, and
+/// are matched first when a character is in the label more than once.
+pub fn fuzzy_score(query: &str, label: &str) -> Option<i32> {
+    let label: Vec<char> = label.to_lowercase().chars().collect();
+    let at_start = |i: usize| i == 0 || !label[i - 1].is_alphanumeric();
+    let mut score = 0;
+    let mut from = 0;
+    let mut last = None;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found: Vec<usize> = (from..label.len()).filter(|i| label[*i] == q).collect();
+        let i = match found.first() {
+            Some(&i) if last.is_some() && i == from => i,
+            _ => found
+                .iter()
+                .copied()
+                .find(|i| at_start(*i))
+                .or(found.first().copied())?,
+        };
+
+        score += 1;
+        if last.is_some_and(|last| last + 1 == i) {
+            score += 5;
+        }
+        if at_start(i) {
+            score += 3;
+        }
+
+        last = Some(i);
+        from = i + 1;
+    }
+
+    Some(score)
+}
+
+pub struct Palette {
+    pub open: bool,
+    pub query: String,
+    style: Style,
+    font: Rc<Font>,
+    matches: Vec<(String, Command)>,
+    selected: usize,
+}
+
+impl Palette {
+    pub fn new(style: &Style, font: Rc<Font>) -> Self {
+        Palette {
+            open: false,
+            query: String::new(),
+            font,
+            matches: vec![],
+            selected: 0,
+            style: Style {
+                x: Value::Relative(Box::new(|this| {
+                    (window::screen_width() - this.width()) / 2.0
+                })),
+                y: Value::Relative(Box::new(|_| window::screen_height() * 0.15)),
+                width: Value::Relative(Box::new(|_| window::screen_width() * 0.5)),
+                font_size: Rc::clone(&style.font_size),
+                theme: Theme {
+                    bg: Rc::clone(&style.theme.bg),
+                    ghost: Rc::clone(&style.theme.ghost),
+                    text: Rc::clone(&style.theme.text),
+                    error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
+                },
+                ..Style::default()
+            },
+        }
+    }
+
+    pub fn input(&mut self, event: Event) -> Option<Command> {
+        match event {
+            Event::Key(KeyCode::Escape, _) => self.close(),
+            Event::Key(KeyCode::Enter, _) => {
+                let command = self.matches.get(self.selected).map(|(_, c)| c.clone());
+                self.close();
+                return command;
+            }
+            Event::Key(KeyCode::Down, _) => self.selected += 1,
+            Event::Key(KeyCode::Tab, mods) if !mods.shift => self.selected += 1,
+            Event::Key(KeyCode::Up, _) | Event::Key(KeyCode::Tab, _) => {
+                self.selected = self.selected.saturating_sub(1)
+            }
+            Event::Key(KeyCode::Backspace, _) => {
+                self.query.pop();
+                self.selected = 0;
+            }
+            Event::Char(c) => {
+                self.query.push(c);
+                self.selected = 0;
+            }
+            _ => (),
+        }
+
+        None
+    }
+
+    pub fn toggle(&mut self) {
+        if self.open {
+            self.close();
+        } else {
+            self.open = true;
+        }
+    }
+
+    fn close(&mut self) {
+        self.open = false;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    pub fn filter(&mut self, commands: Vec<(String, Command)>) {
+        let mut scored: Vec<(i32, (String, Command))> = commands
+            .into_iter()
+            .filter_map(|c| fuzzy_score(&self.query, &c.0).map(|score| (score, c)))
+            .collect();
+        // stable, so equal scores keep their order
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+        self.matches = scored.into_iter().map(|(_, c)| c).collect();
+        self.selected = self.selected.min(self.matches.len().saturating_sub(1));
+    }
+
+    pub fn update(&self) {
+        if !self.open {
+            return;
+        }
+
+        let fsize = *self.style.font_size.borrow();
+        let (x, y, width) = (self.style.x(), self.style.y(), self.style.width());
+        let row = fsize * 1.6;
+        let pad = fsize * 0.4;
+
+        let bg = *self.style.theme.bg.borrow();
+        let text = *self.style.theme.text.borrow();
+        let ghost = *self.style.theme.ghost.borrow();
+
+        // dims the screen below
+        shapes::draw_rectangle(
+            0.0,
+            0.0,
+            window::screen_width(),
+            window::screen_height(),
+            Color { a: 0.7, ..bg },
+        );
+
+        // the selected command stays in view
+        let top = (self.selected + 1).saturating_sub(SHOWN);
+        let shown = self.matches.len().saturating_sub(top).min(SHOWN);
+        let height = row * (shown + 1) as f32 + pad;
+
+        shapes::draw_rectangle(x, y, width, height, bg);
+        shapes::draw_rectangle_lines(x, y, width, height, 2.0, text);
+
+        let lines = std::iter::once((format!("> {}", self.query), text)).chain(
+            self.matches
+                .iter()
+                .enumerate()
+                .skip(top)
+                .take(SHOWN)
+                .map(|(i, (label, _))| {
+                    (label.clone(), if i == self.selected { text } else { ghost })
+                }),
+        );
+        for (i, (line, color)) in lines.enumerate() {
+            crate::app::text::print_text(
+                &self.style,
+                &line,
+                PrintOptions {
+                    x: Some(x + pad),
+                    y: Some(y + pad + i as f32 * row),
+                    font: Some(Rc::clone(&self.font)),
+                    color: Some(color),
+                    ..PrintOptions::default()
+                },
+            );
+        }
+
+        if !self.matches.is_empty() {
+            let selected = (self.selected - top + 1) as f32;
+            shapes::draw_rectangle_lines(
+                x + pad / 2.0,
+                y + pad / 2.0 + selected * row,
+                width - pad,
+                row,
+                1.0,
+                ghost,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy() {
+        assert_eq!(fuzzy_score("", "Restart"), Some(0));
+        assert!(fuzzy_score("tkn", "Change theme: Tokyonight").is_some());
+        assert_eq!(fuzzy_score("nt", "Restart"), None);
+
+        // together and at the start of words beats spread out
+        assert!(fuzzy_score("th", "Change theme: Atom") > fuzzy_score("th", "Mode: Daily Math"));
+        assert!(fuzzy_score("dr", "Mode: Drill") > fuzzy_score("dr", "Mode: Words 25"));
+        assert_eq!(fuzzy_score("w 50", "Mode: Words 100"), None);
+        assert_eq!(
+            fuzzy_score("FONT", "Font size +"),
+            fuzzy_score("font", "font size +")
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::rc::Rc;
+
+use macroquad::input::{self, KeyCode};
+use macroquad::window;
+
+use super::events::Event;
+use super::focus::{Focus, Nav, QuoteSearchFocus::*};
+use super::widget::Widget;
+use super::{App, Screen};
+
+mod results;
+mod search_box;
+
+pub async fn run(app: &mut App) {
+    input::show_mouse(true);
+    input::clear_input_queue();
+    app.events.drain();
+
+    let mut focus = Focus::new(vec![SearchBox, Results, CancelButton], Some(SearchBox))
+        .with_typing(vec![SearchBox]);
+
+    let mut search_box = search_box::SearchBox::new(&app.style, Rc::clone(&app.font));
+    let mut results = results::Results::new(&app.style, Rc::clone(&app.font));
+    let mut cancel_button = Widget::button(
+        CancelButton,
+        "Cancel (ESC)",
+        &app.style,
+        Rc::clone(&app.font),
+    );
+
+    let mut matches = app.data.search_quotes(&search_box.query);
+
+    loop {
+        cancel_button.center(window::screen_height() - 2.0 * *app.style.font_size.borrow() - 30.0);
+
+        let events = app.read_events();
+        if app.state.screen != Screen::QuoteSearch {
+            return;
+        }
+        for (event, _) in events {
+            // the arrows go through the results rather than the focus
+            match event {
+                Event::Key(KeyCode::Up, _) => {
+                    focus.set(Some(Results));
+                    results.select(results.selected.saturating_sub(1), matches.len());
+                    continue;
+                }
+                Event::Key(KeyCode::Down, _) => {
+                    focus.set(Some(Results));
+                    results.select(results.selected + 1, matches.len());
+                    continue;
+                }
+                _ => (),
+            }
+
+            match focus.handle(event, &app.config.keybindings) {
+                Nav::Moved => continue,
+                Nav::Activated(CancelButton) => {
+                    app.state.screen = Screen::TypingTest;
+                    return;
+                }
+                Nav::Activated(SearchBox | Results) => {
+                    if let Some(&id) = matches.get(results.selected) {
+                        select(app, id);
+                        return;
+                    }
+                    continue;
+                }
+                Nav::Ignored => (),
+            }
+
+            match event {
+                Event::Key(KeyCode::Escape, _) => {
+                    app.state.screen = Screen::TypingTest;
+                    return;
+                }
+                Event::Key(KeyCode::Backspace, _) => {
+                    focus.set(Some(SearchBox));
+                    search_box.query.pop();
+                    matches = app.data.search_quotes(&search_box.query);
+                    results.reset();
+                }
+                Event::Key(KeyCode::Enter, _) => {
+                    if let Some(&id) = matches.get(results.selected) {
+                        select(app, id);
+                        return;
+                    }
+                }
+                Event::Key(key, mods) => {
+                    if let Some(action) = app.config.keybindings.action_while_typing(key, mods) {
+                        app.font_action(action);
+                    }
+                }
+                Event::Action(_) => (),
+                Event::Char(c) => {
+                    focus.set(Some(SearchBox));
+                    search_box.query.push(c);
+                    matches = app.data.search_quotes(&search_box.query);
+                    results.reset();
+                }
+            }
+        }
+
+        let (_, wheel_y) = input::mouse_wheel();
+        if wheel_y > 0.0 {
+            results.select(results.selected.saturating_sub(1), matches.len());
+        } else if wheel_y < 0.0 {
+            results.select(results.selected + 1, matches.len());
+        }
+
+        let row = results.hovered(matches.len());
+        if let Some(row) = row {
+            if input::mouse_delta_position().length() > 0.0 {
+                results.select(row, matches.len());
+            }
+        }
+        let hovered = row.map(|_| Results).or_else(|| cancel_button.hovered());
+        match focus.mouse(hovered) {
+            Some(Results) => {
+                select(app, matches[results.selected]);
+                return;
+            }
+            Some(CancelButton) => {
+                app.state.screen = Screen::TypingTest;
+                return;
+            }
+            _ => (),
+        }
+
+        window::clear_background(*app.style.theme.bg.borrow());
+
+        search_box.update(focus.is(SearchBox));
+        results.update(app.data.quotes(), &matches, focus.is(Results));
+        cancel_button.draw(focus.get());
+        app.palette.update();
+
+        window::next_frame().await;
+    }
+}
+
+fn select(app: &mut App, id: usize) {
+    app.load_quote(id);
+    app.state.screen = Screen::TypingTest;
+}
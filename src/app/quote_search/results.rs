@@ -0,0 +1,191 @@
+use std::rc::Rc;
+
+use macroquad::text::{self, Font};
+use macroquad::window;
+
+use crate::app::text::PrintOptions;
+use crate::app::theme::Theme;
+use crate::app::{self, BorderParams, Style, Value};
+use crate::data_provider::Quote;
+
+pub struct Results {
+    pub style: Style,
+    pub selected: usize,
+    scroll: usize,
+    font: Rc<Font>,
+}
+
+impl Results {
+    pub fn new(style: &Style, font: Rc<Font>) -> Self {
+        let f1 = Rc::clone(&style.font_size);
+        let f2 = Rc::clone(&style.font_size);
+
+        Results {
+            font,
+            selected: 0,
+            scroll: 0,
+            style: Style {
+                border: Some(BorderParams {
+                    size: 2.0,
+                    color: Rc::clone(&style.theme.text),
+                }),
+                x: Value::Relative(Box::new(|this| {
+                    (window::screen_width() - this.width()) / 2.0
+                })),
+                y: Value::Relative(Box::new(move |_| {
+                    window::screen_height() * 0.08 + *f1.borrow() + 40.0
+                })),
+                width: Value::Relative(Box::new(|_| window::screen_width() * 0.7)),
+                height: Value::Relative(Box::new(move |this| {
+                    window::screen_height() - this.y() - 3.0 * *f2.borrow() - 40.0
+                })),
+                font_size: Rc::clone(&style.font_size),
+                theme: Theme {
+                    bg: Rc::clone(&style.theme.bg),
+                    ghost: Rc::clone(&style.theme.ghost),
+                    text: Rc::clone(&style.theme.text),
+                    error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
+                },
+                padding_x: Some(Value::Absolute(10.0)),
+                padding_y: Some(Value::Absolute(5.0)),
+                ..Style::default()
+            },
+        }
+    }
+
+    pub fn row_height(&self) -> f32 {
+        *self.style.font_size.borrow() * 1.7 + 2.0 * self.style.padding_y()
+    }
+
+    pub fn visible_rows(&self) -> usize {
+        ((self.style.height() / self.row_height()) as usize).max(1)
+    }
+
+    pub fn select(&mut self, selected: usize, len: usize) {
+        self.selected = if len == 0 { 0 } else { selected.min(len - 1) };
+
+        let visible = self.visible_rows();
+
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + visible {
+            self.scroll = self.selected + 1 - visible;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.selected = 0;
+        self.scroll = 0;
+    }
+
+    pub fn hovered(&self, len: usize) -> Option<usize> {
+        let (x, y) = macroquad::input::mouse_position();
+
+        if x < self.style.x()
+            || x > self.style.x() + self.style.width()
+            || y < self.style.y()
+            || y > self.style.y() + self.style.height()
+        {
+            return None;
+        }
+
+        let row = self.scroll + ((y - self.style.y()) / self.row_height()) as usize;
+
+        if row < len && row < self.scroll + self.visible_rows() {
+            Some(row)
+        } else {
+            None
+        }
+    }
+
+    pub fn update(&self, quotes: &[Quote], matches: &[usize], focused: bool) {
+        let fsize = *self.style.font_size.borrow();
+        let row_height = self.row_height();
+        let max_width = self.style.width() - 2.0 * self.style.padding_x();
+
+        if matches.is_empty() {
+            app::text::print_text(
+                &self.style,
+                "No quotes found",
+                PrintOptions {
+                    font: Some(Rc::clone(&self.font)),
+                    color: Some(*self.style.theme.ghost.borrow()),
+                    ..PrintOptions::default()
+                },
+            );
+            return;
+        }
+
+        for (row, &id) in matches
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(self.visible_rows())
+        {
+            let quote = &quotes[id];
+            let y = self.style.y() + (row - self.scroll) as f32 * row_height;
+
+            let color = if row == self.selected {
+                *self.style.theme.text.borrow()
+            } else {
+                *self.style.theme.ghost.borrow()
+            };
+
+            app::text::print_text(
+                &self.style,
+                &self.truncate(&format!("#{} {}", id, quote.quote), max_width, 1.0),
+                PrintOptions {
+                    y: Some(y),
+                    font: Some(Rc::clone(&self.font)),
+                    color: Some(color),
+                    ..PrintOptions::default()
+                },
+            );
+
+            app::text::print_text(
+                &self.style,
+                &self.truncate(&quote.source, max_width, 0.7),
+                PrintOptions {
+                    y: Some(y + fsize),
+                    font: Some(Rc::clone(&self.font)),
+                    font_scale: Some(0.7),
+                    color: Some(*self.style.theme.ghost.borrow()),
+                    ..PrintOptions::default()
+                },
+            );
+
+            if row == self.selected && focused {
+                macroquad::shapes::draw_rectangle_lines(
+                    self.style.x(),
+                    y,
+                    self.style.width(),
+                    row_height,
+                    2.0,
+                    *self.style.theme.text.borrow(),
+                );
+            }
+        }
+    }
+
+    fn truncate(&self, s: &str, max_width: f32, font_scale: f32) -> String {
+        let fsize = *self.style.font_size.borrow() as u16;
+        let measure = |s: &str| text::measure_text(s, Some(&self.font), fsize, font_scale).width;
+
+        if measure(s) <= max_width {
+            return s.to_string();
+        }
+
+        let mut out = String::new();
+
+        for c in s.chars() {
+            out.push(c);
+            if measure(&(out.clone() + "...")) > max_width {
+                out.pop();
+                break;
+            }
+        }
+
+        out + "..."
+    }
+}
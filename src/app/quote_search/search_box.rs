@@ -0,0 +1,91 @@
+use std::rc::Rc;
+
+use macroquad::text::{self, Font};
+use macroquad::window;
+
+use crate::app::text::PrintOptions;
+use crate::app::theme::Theme;
+use crate::app::{self, BorderParams, Style, Value};
+
+pub struct SearchBox {
+    pub style: Style,
+    pub query: String,
+    font: Rc<Font>,
+}
+
+impl SearchBox {
+    pub fn new(style: &Style, font: Rc<Font>) -> Self {
+        let f1 = Rc::clone(&style.font_size);
+
+        SearchBox {
+            font,
+            query: String::new(),
+            style: Style {
+                border: Some(BorderParams {
+                    size: 2.0,
+                    color: Rc::clone(&style.theme.ghost),
+                }),
+                x: Value::Relative(Box::new(|this| {
+                    (window::screen_width() - this.width()) / 2.0
+                })),
+                y: Value::Relative(Box::new(|_| window::screen_height() * 0.08)),
+                width: Value::Relative(Box::new(|_| window::screen_width() * 0.7)),
+                height: Value::Relative(Box::new(move |this| {
+                    *f1.borrow() + 2.0 * this.padding_y()
+                })),
+                font_size: Rc::clone(&style.font_size),
+                theme: Theme {
+                    bg: Rc::clone(&style.theme.bg),
+                    ghost: Rc::clone(&style.theme.ghost),
+                    text: Rc::clone(&style.theme.text),
+                    error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
+                },
+                padding_x: Some(Value::Absolute(10.0)),
+                padding_y: Some(Value::Absolute(10.0)),
+                ..Style::default()
+            },
+        }
+    }
+
+    pub fn update(&self, focused: bool) {
+        self.style.draw_border();
+
+        let fsize = *self.style.font_size.borrow();
+
+        if self.query.is_empty() {
+            app::text::print_text(
+                &self.style,
+                "Search quotes and sources",
+                PrintOptions {
+                    font: Some(Rc::clone(&self.font)),
+                    color: Some(*self.style.theme.ghost.borrow()),
+                    ..PrintOptions::default()
+                },
+            );
+        } else {
+            app::text::print_text(
+                &self.style,
+                &self.query,
+                PrintOptions {
+                    font: Some(Rc::clone(&self.font)),
+                    ..PrintOptions::default()
+                },
+            );
+        }
+
+        if focused {
+            let width = text::measure_text(&self.query, Some(&self.font), fsize as u16, 1.0).width;
+
+            app::text::print_text(
+                &self.style,
+                "|",
+                PrintOptions {
+                    x: Some(self.style.x() + width),
+                    font: Some(Rc::clone(&self.font)),
+                    ..PrintOptions::default()
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use macroquad::input::{self, KeyCode};
+use macroquad::window;
+
+use super::events::Event;
+use super::widget::Widget;
+use super::{App, AppState, Screen};
+
+pub async fn run(app: &mut App) {
+    input::show_mouse(true);
+    input::clear_input_queue();
+    app.events.drain();
+
+    let started = Instant::now();
+    let rest = Duration::from_secs(app.config.sprint.rest);
+
+    let done = app.state.sprints.len();
+    let round = match app.state.sprints.last() {
+        Some(sprint) => format!(
+            "Round {} of {}, {:.1} wpm {}%",
+            done, app.config.sprint.rounds, sprint.wpm, sprint.accuracy
+        ),
+        None => format!("Round {} of {}", done, app.config.sprint.rounds),
+    };
+
+    let label = |text: &str, color| Widget::label(text, color, &app.style, Rc::clone(&app.font));
+    let mut lines: Widget<()> = Widget::column(
+        &app.style,
+        1.0,
+        vec![
+            label(&round, &app.style.theme.text),
+            // the countdown, set every frame
+            label("", &app.style.theme.ghost),
+            label(
+                "<Enter> to start now, <Esc> to stop",
+                &app.style.theme.ghost,
+            ),
+        ],
+    );
+
+    'rest: while started.elapsed() < rest {
+        let events = app.read_events();
+        if app.state.screen != Screen::Rest {
+            return;
+        }
+        for (event, _) in events {
+            if let Event::Key(key, mods) = event {
+                if let Some(action) = app.config.keybindings.action(key, mods) {
+                    app.font_action(action);
+                }
+            }
+
+            match event {
+                Event::Key(KeyCode::Enter | KeyCode::Space, _) => break 'rest,
+                Event::Key(KeyCode::Escape, _) => {
+                    stop(&mut app.state);
+                    return;
+                }
+                _ => (),
+            }
+        }
+
+        let left = rest.saturating_sub(started.elapsed()).as_secs_f32().ceil();
+        let fsize = *app.style.font_size.borrow();
+
+        window::clear_background(*app.style.theme.bg.borrow());
+
+        if let Some(countdown) = lines.child_mut(1) {
+            countdown.set_text(&format!("next sprint in {}", left));
+        }
+        lines.center(window::screen_height() / 2.0 - 3.0 * fsize);
+        lines.draw(None);
+        app.palette.update();
+
+        window::next_frame().await;
+    }
+
+    app.next_text();
+    app.state.screen = Screen::TypingTest;
+}
+
+fn stop(state: &mut AppState) {
+    state.screen = Screen::End;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{Mode, Sprint};
+
+    #[test]
+    fn stopping() {
+        let mut state = AppState {
+            mode: Mode::Sprint {
+                s: "a b".to_string(),
+            },
+            screen: Screen::Rest,
+            sprints: vec![Sprint {
+                samples: vec![],
+                wpm: 50.0,
+                accuracy: 90,
+            }],
+            ..AppState::default()
+        };
+
+        stop(&mut state);
+
+        // the rounds so far are left for the end screen
+        assert_eq!(state.screen, Screen::End);
+        assert_eq!(state.sprints.len(), 1);
+    }
+}
@@ -0,0 +1,136 @@
+use std::rc::Rc;
+
+use macroquad::text::{self, Font};
+use macroquad::window;
+
+use super::rows::Row;
+use crate::app::text::PrintOptions;
+use crate::app::theme::Theme;
+use crate::app::{Style, Value};
+use crate::Config;
+
+pub struct List {
+    pub style: Style,
+    font: Rc<Font>,
+    top: usize,
+}
+
+impl List {
+    pub fn new(style: &Style, font: Rc<Font>) -> Self {
+        List {
+            font,
+            top: 0,
+            style: Style {
+                x: Value::Relative(Box::new(|_| window::screen_width() * 0.15)),
+                y: Value::Relative(Box::new(|_| window::screen_height() * 0.15)),
+                width: Value::Relative(Box::new(|_| window::screen_width() * 0.7)),
+                height: Value::Relative(Box::new(|_| window::screen_height() * 0.7)),
+                font_size: Rc::clone(&style.font_size),
+                theme: Theme {
+                    bg: Rc::clone(&style.theme.bg),
+                    ghost: Rc::clone(&style.theme.ghost),
+                    text: Rc::clone(&style.theme.text),
+                    error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
+                },
+                ..Style::default()
+            },
+        }
+    }
+
+    fn row_height(&self) -> f32 {
+        *self.style.font_size.borrow() * 1.8
+    }
+
+    fn shown(&self) -> usize {
+        ((self.style.height() / self.row_height()) as usize).max(1)
+    }
+
+    pub fn hovered(&self, rows: usize) -> Option<usize> {
+        let (x, y) = macroquad::input::mouse_position();
+
+        if x < self.style.x() || x > self.style.x() + self.style.width() || y < self.style.y() {
+            return None;
+        }
+
+        let i = ((y - self.style.y()) / self.row_height()) as usize;
+        (i < self.shown())
+            .then_some(self.top + i)
+            .filter(|i| *i < rows)
+    }
+
+    pub fn update(&mut self, rows: &[Row], config: &Config, selected: usize, capturing: bool) {
+        let shown = self.shown();
+        if selected < self.top {
+            self.top = selected;
+        } else if selected >= self.top + shown {
+            self.top = selected + 1 - shown;
+        }
+
+        let height = self.row_height();
+        let fsize = *self.style.font_size.borrow();
+        let (x, width) = (self.style.x(), self.style.width());
+        let pad = fsize * 0.4;
+
+        for (i, row) in rows.iter().enumerate().skip(self.top).take(shown) {
+            let y = self.style.y() + (i - self.top) as f32 * height;
+            let is_selected = i == selected;
+
+            let value = if is_selected && capturing {
+                "press a key".to_string()
+            } else {
+                row.value(config)
+            };
+            let (name_color, value_color) = match (is_selected, capturing) {
+                (true, true) => (
+                    *self.style.theme.text.borrow(),
+                    *self.style.theme.error.borrow(),
+                ),
+                (true, false) => (
+                    *self.style.theme.text.borrow(),
+                    *self.style.theme.text.borrow(),
+                ),
+                _ => (
+                    *self.style.theme.ghost.borrow(),
+                    *self.style.theme.ghost.borrow(),
+                ),
+            };
+
+            crate::app::text::print_text(
+                &self.style,
+                &row.name(),
+                PrintOptions {
+                    x: Some(x + pad),
+                    y: Some(y + pad),
+                    font: Some(Rc::clone(&self.font)),
+                    color: Some(name_color),
+                    ..PrintOptions::default()
+                },
+            );
+
+            let value_width = text::measure_text(&value, Some(&self.font), fsize as u16, 1.0).width;
+            crate::app::text::print_text(
+                &self.style,
+                &value,
+                PrintOptions {
+                    x: Some(x + width - pad - value_width),
+                    y: Some(y + pad),
+                    font: Some(Rc::clone(&self.font)),
+                    color: Some(value_color),
+                    ..PrintOptions::default()
+                },
+            );
+
+            if is_selected {
+                macroquad::shapes::draw_rectangle_lines(
+                    x,
+                    y,
+                    width,
+                    height - pad / 2.0,
+                    2.0,
+                    *self.style.theme.text.borrow(),
+                );
+            }
+        }
+    }
+}
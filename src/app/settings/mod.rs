@@ -0,0 +1,192 @@
+use std::rc::Rc;
+
+use macroquad::input::{self, KeyCode, MouseButton};
+use macroquad::{text, window};
+
+use super::events::Event;
+use super::focus::{Focus, Nav};
+use super::text::PrintOptions;
+use super::{Action, App, KeyCombo, Keybindings, Screen};
+
+mod list;
+mod rows;
+
+use rows::Row;
+
+pub async fn run(app: &mut App) {
+    input::show_mouse(true);
+    input::clear_input_queue();
+    app.events.drain();
+
+    let rows = rows::rows();
+    let mut focus = Focus::new((0..rows.len()).collect(), Some(0)).with_arrows();
+    // the next key combo pressed is added to the selected binding
+    let mut capturing = false;
+
+    let mut list = list::List::new(&app.style, Rc::clone(&app.font));
+
+    loop {
+        let events = app.read_events();
+        if app.state.screen != Screen::Settings {
+            return;
+        }
+        for (event, _) in events {
+            let Event::Key(key, mods) = event else {
+                continue;
+            };
+
+            if capturing {
+                // Escape goes back to the list rather than being bound
+                if key == KeyCode::Escape {
+                    capturing = false;
+                    continue;
+                }
+
+                let combo = KeyCombo::new(key, mods);
+                // modifiers on their own and keys without a name in the config are
+                // ignored until a key that can be saved comes
+                if combo.to_string().parse::<KeyCombo>() != Ok(combo) {
+                    continue;
+                }
+
+                if let Row::Binding(action) = rows[selected(&focus)] {
+                    let combos = app.config.keybindings.combos_mut(action);
+                    if !combos.contains(&combo) {
+                        combos.push(combo);
+                    }
+                    app.config.update_file();
+                }
+                capturing = false;
+                continue;
+            }
+
+            // the arrows sideways change the value rather than move the focus
+            let selected = selected(&focus);
+            match key {
+                KeyCode::Right => {
+                    change(app, &rows[selected], true);
+                    continue;
+                }
+                KeyCode::Left => {
+                    change(app, &rows[selected], false);
+                    continue;
+                }
+                _ => (),
+            }
+
+            match focus.handle(event, &app.config.keybindings) {
+                Nav::Moved => continue,
+                Nav::Activated(row) => {
+                    match rows[row] {
+                        Row::Binding(_) => capturing = true,
+                        Row::Setting(_) => change(app, &rows[row], true),
+                    }
+                    continue;
+                }
+                Nav::Ignored => (),
+            }
+
+            match app.config.keybindings.action(key, mods) {
+                Some(action @ (Action::FontUp | Action::FontDown | Action::FontReset)) => {
+                    app.font_action(action)
+                }
+                _ => match key {
+                    KeyCode::Escape => {
+                        leave(app);
+                        return;
+                    }
+                    KeyCode::Backspace => {
+                        if let Row::Binding(action) = rows[selected] {
+                            app.config.keybindings.combos_mut(action).pop();
+                            app.config.update_file();
+                        }
+                    }
+                    KeyCode::Delete => {
+                        if let Row::Binding(action) = rows[selected] {
+                            *app.config.keybindings.combos_mut(action) =
+                                Keybindings::default().combos(action).clone();
+                            app.config.update_file();
+                        }
+                    }
+                    _ => (),
+                },
+            }
+        }
+
+        if !capturing {
+            // a row stays selected when the mouse leaves the list
+            if let Some(row) = list.hovered(rows.len()) {
+                if focus.mouse(Some(row)).is_some() {
+                    match rows[row] {
+                        Row::Binding(_) => capturing = true,
+                        Row::Setting(_) => change(app, &rows[row], true),
+                    }
+                } else if input::is_mouse_button_pressed(MouseButton::Right) {
+                    focus.set(Some(row));
+                    change(app, &rows[row], false);
+                }
+            }
+
+            match input::mouse_wheel() {
+                (_, dy) if dy < 0.0 => focus.next(),
+                (_, dy) if dy > 0.0 => focus.previous(),
+                _ => (),
+            }
+        }
+
+        window::clear_background(*app.style.theme.bg.borrow());
+
+        list.update(&rows, &app.config, selected(&focus), capturing);
+
+        let fsize = *app.style.font_size.borrow();
+        let hint = match rows[selected(&focus)] {
+            Row::Binding(_) if capturing => "Press the keys to add, <Esc> to cancel",
+            Row::Binding(_) => {
+                "<Enter> to add keys, <Backspace> to remove the last, <Delete> to reset, <Esc> to go back"
+            }
+            Row::Setting(_) => "<Left> and <Right> to change, <Esc> to go back",
+        };
+        let width = text::measure_text(hint, Some(&app.font), (fsize * 0.7) as u16, 1.0).width;
+        super::text::print_text(
+            &app.style,
+            hint,
+            PrintOptions {
+                x: Some((window::screen_width() - width) / 2.0),
+                y: Some(window::screen_height() * 0.9),
+                font: Some(Rc::clone(&app.font)),
+                font_size: Some(fsize * 0.7),
+                color: Some(*app.style.theme.ghost.borrow()),
+                ..PrintOptions::default()
+            },
+        );
+        app.palette.update();
+
+        window::next_frame().await;
+    }
+}
+
+fn selected(focus: &Focus<usize>) -> usize {
+    focus.get().unwrap_or(0)
+}
+
+fn change(app: &mut App, row: &Row, forward: bool) {
+    let Row::Setting(setting) = row else {
+        return;
+    };
+
+    (setting.step)(&mut app.config, forward);
+    app.config.update_file();
+
+    app.style.theme.set(&app.config.theme);
+    *app.style.font_size.borrow_mut() = app.config.font_size;
+    app.events.set_layout(&app.config.keyboard.layout());
+}
+
+fn leave(app: &mut App) {
+    if app.config.mode != app.state.mode {
+        let mode = app.config.mode.clone();
+        app.set_mode(&mode);
+    }
+
+    app.state.screen = Screen::TypingTest;
+}
@@ -0,0 +1,358 @@
+use std::mem;
+
+use crate::app::{Action, CaretStyle, LayoutName, Mode, ThemeName};
+use crate::data_provider::Quote;
+use crate::Config;
+
+pub struct Setting {
+    pub name: &'static str,
+    pub value: fn(&Config) -> String,
+    pub step: fn(&mut Config, bool),
+}
+
+pub enum Row {
+    Setting(Setting),
+    Binding(Action),
+}
+
+impl Row {
+    pub fn name(&self) -> String {
+        match self {
+            Row::Setting(s) => s.name.to_string(),
+            Row::Binding(action) => format!("Key: {}", action),
+        }
+    }
+
+    pub fn value(&self, config: &Config) -> String {
+        match self {
+            Row::Setting(s) => (s.value)(config),
+            Row::Binding(action) => {
+                let combos = config.keybindings.combos(*action);
+                if combos.is_empty() {
+                    "none".to_string()
+                } else {
+                    combos
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                }
+            }
+        }
+    }
+}
+
+fn cycle<T: PartialEq + Clone>(options: &[T], current: &T, forward: bool) -> T {
+    cycle_by(options, |o| o == current, forward)
+}
+
+fn cycle_by<T: Clone>(options: &[T], is_current: impl Fn(&T) -> bool, forward: bool) -> T {
+    let n = options.len();
+    let next = match options.iter().position(is_current) {
+        Some(i) if forward => (i + 1) % n,
+        Some(i) => (i + n - 1) % n,
+        None => 0,
+    };
+    options[next].clone()
+}
+
+fn on_off(b: bool) -> String {
+    if b { "on" } else { "off" }.to_string()
+}
+
+fn or_off<T: ToString>(v: Option<T>) -> String {
+    v.map_or_else(|| "off".to_string(), |v| v.to_string())
+}
+
+const THEMES: [ThemeName; 4] = [
+    ThemeName::Atom,
+    ThemeName::Catppuccin,
+    ThemeName::Gruvbox,
+    ThemeName::Tokyonight,
+];
+
+const LAYOUTS: [LayoutName; 4] = [
+    LayoutName::Qwerty,
+    LayoutName::Dvorak,
+    LayoutName::Colemak,
+    LayoutName::Workman,
+];
+
+const CARETS: [CaretStyle; 5] = [
+    CaretStyle::Line,
+    CaretStyle::Block,
+    CaretStyle::Underline,
+    CaretStyle::Outline,
+    CaretStyle::Off,
+];
+
+const CARET_COLORS: [Option<u32>; 5] = [
+    None,
+    Some(0xffffff),
+    Some(0xfabd2f),
+    Some(0x7aa2f7),
+    Some(0xf38ba8),
+];
+
+const WORD_COUNTS: [usize; 5] = [10, 25, 50, 100, 200];
+
+fn modes() -> Vec<Mode> {
+    let s = String::new();
+
+    vec![
+        Mode::Words {
+            n: 25,
+            s: s.clone(),
+        },
+        Mode::Quote(Quote {
+            source: s.clone(),
+            quote: s.clone(),
+        }),
+        Mode::Daily {
+            day: 0,
+            s: s.clone(),
+        },
+        Mode::Zen { s: s.clone() },
+        Mode::Drill {
+            ngram: s.clone(),
+            repetitions: 0,
+            s: s.clone(),
+        },
+        Mode::Lesson {
+            keys: s.clone(),
+            s: s.clone(),
+        },
+        Mode::Sprint { s },
+    ]
+}
+
+pub fn rows() -> Vec<Row> {
+    let settings = [
+        Setting {
+            name: "Theme",
+            value: |c| format!("{:?}", c.theme),
+            step: |c, f| c.theme = cycle(&THEMES, &c.theme, f),
+        },
+        Setting {
+            name: "Font size",
+            value: |c| c.font_size.to_string(),
+            step: |c, f| c.font_size = (c.font_size + if f { 2.0 } else { -2.0 }).clamp(8.0, 96.0),
+        },
+        Setting {
+            name: "Caret",
+            value: |c| format!("{:?}", c.caret.style),
+            step: |c, f| c.caret.style = cycle(&CARETS, &c.caret.style, f),
+        },
+        Setting {
+            name: "Smooth caret",
+            value: |c| on_off(c.caret.smooth),
+            step: |c, _| c.caret.smooth = !c.caret.smooth,
+        },
+        Setting {
+            name: "Blinking caret",
+            value: |c| on_off(c.caret.blink),
+            step: |c, _| c.caret.blink = !c.caret.blink,
+        },
+        Setting {
+            name: "Caret color",
+            value: |c| match c.caret.color {
+                Some(color) => format!("#{:06x}", color),
+                None => "theme".to_string(),
+            },
+            step: |c, f| c.caret.color = cycle(&CARET_COLORS, &c.caret.color, f),
+        },
+        Setting {
+            name: "Mode",
+            value: |c| c.mode.get_name(),
+            step: |c, f| {
+                // whatever the word count and text, a mode is where its kind is
+                let kind = mem::discriminant(&c.mode);
+                c.mode = cycle_by(&modes(), |m| mem::discriminant(m) == kind, f);
+            },
+        },
+        Setting {
+            name: "Words",
+            value: |c| match c.mode {
+                Mode::Words { n, .. } => n.to_string(),
+                _ => "in words mode".to_string(),
+            },
+            step: |c, f| {
+                let n = match c.mode {
+                    Mode::Words { n, .. } => cycle(&WORD_COUNTS, &n, f),
+                    _ => WORD_COUNTS[0],
+                };
+                c.mode = Mode::Words {
+                    n,
+                    s: String::new(),
+                };
+            },
+        },
+        Setting {
+            name: "Resume unfinished tests",
+            value: |c| on_off(c.resume),
+            step: |c, _| c.resume = !c.resume,
+        },
+        Setting {
+            name: "Restart on first error",
+            value: |c| on_off(c.auto_restart_on_error),
+            step: |c, _| c.auto_restart_on_error = !c.auto_restart_on_error,
+        },
+        Setting {
+            name: "Sudden death",
+            value: |c| on_off(c.fail.sudden_death),
+            step: |c, _| c.fail.sudden_death = !c.fail.sudden_death,
+        },
+        Setting {
+            name: "Fail below accuracy",
+            value: |c| or_off(c.fail.min_accuracy.map(|a| format!("{}%", a))),
+            step: |c, f| {
+                c.fail.min_accuracy = cycle(
+                    &[None, Some(80), Some(90), Some(95), Some(98), Some(100)],
+                    &c.fail.min_accuracy,
+                    f,
+                )
+            },
+        },
+        Setting {
+            name: "Fail below wpm",
+            value: |c| or_off(c.fail.min_wpm),
+            step: |c, f| {
+                c.fail.min_wpm = cycle(
+                    &[
+                        None,
+                        Some(20.0),
+                        Some(40.0),
+                        Some(60.0),
+                        Some(80.0),
+                        Some(100.0),
+                    ],
+                    &c.fail.min_wpm,
+                    f,
+                )
+            },
+        },
+        Setting {
+            name: "Keyboard layout",
+            value: |c| match &c.keyboard.file {
+                Some(file) => file.display().to_string(),
+                None => format!("{:?}", c.keyboard.layout),
+            },
+            step: |c, f| {
+                c.keyboard.layout = cycle(&LAYOUTS, &c.keyboard.layout, f);
+                c.keyboard.file = None;
+            },
+        },
+        Setting {
+            name: "Show keyboard",
+            value: |c| on_off(c.keyboard.show),
+            step: |c, _| c.keyboard.show = !c.keyboard.show,
+        },
+        Setting {
+            name: "Practice repeats",
+            value: |c| c.practice.repeat.to_string(),
+            step: |c, f| c.practice.repeat = step(c.practice.repeat, 1, f).clamp(1, 10),
+        },
+        Setting {
+            name: "Practice slowest words",
+            value: |c| c.practice.slowest.to_string(),
+            step: |c, f| c.practice.slowest = step(c.practice.slowest, 1, f).min(20),
+        },
+        Setting {
+            name: "Session tests",
+            value: |c| c.session.tests.to_string(),
+            step: |c, f| c.session.tests = step(c.session.tests, 1, f).clamp(1, 50),
+        },
+        Setting {
+            name: "Session minutes",
+            value: |c| or_off(c.session.minutes),
+            step: |c, f| {
+                c.session.minutes = match (c.session.minutes, f) {
+                    (None, true) => Some(5),
+                    (Some(5), false) => None,
+                    (Some(m), _) => Some(step(m as usize, 5, f).min(120) as u64),
+                    (None, false) => None,
+                }
+            },
+        },
+        Setting {
+            name: "Sprint seconds",
+            value: |c| c.sprint.seconds.to_string(),
+            step: |c, f| {
+                c.sprint.seconds = step(c.sprint.seconds as usize, 5, f).clamp(5, 300) as u64
+            },
+        },
+        Setting {
+            name: "Sprint rest seconds",
+            value: |c| c.sprint.rest.to_string(),
+            step: |c, f| c.sprint.rest = step(c.sprint.rest as usize, 5, f).min(300) as u64,
+        },
+        Setting {
+            name: "Sprint rounds",
+            value: |c| c.sprint.rounds.to_string(),
+            step: |c, f| c.sprint.rounds = step(c.sprint.rounds, 1, f).clamp(1, 20),
+        },
+        Setting {
+            name: "Drill repetitions",
+            value: |c| c.drill.repetitions.to_string(),
+            step: |c, f| c.drill.repetitions = step(c.drill.repetitions, 1, f).clamp(1, 50),
+        },
+        Setting {
+            name: "Drill target wpm",
+            value: |c| c.drill.target_wpm.to_string(),
+            step: |c, f| {
+                c.drill.target_wpm = (c.drill.target_wpm + if f { 5.0 } else { -5.0 }).max(0.0)
+            },
+        },
+        Setting {
+            name: "Lesson target wpm",
+            value: |c| c.lesson.target_wpm.to_string(),
+            step: |c, f| {
+                c.lesson.target_wpm = (c.lesson.target_wpm + if f { 5.0 } else { -5.0 }).max(0.0)
+            },
+        },
+    ];
+
+    settings
+        .into_iter()
+        .map(Row::Setting)
+        .chain(Action::ALL.into_iter().map(Row::Binding))
+        .collect()
+}
+
+fn step(n: usize, by: usize, forward: bool) -> usize {
+    if forward {
+        n + by
+    } else {
+        n.saturating_sub(by)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycling() {
+        assert_eq!(cycle(&[1, 2, 3], &3, true), 1);
+        assert_eq!(cycle(&[1, 2, 3], &1, false), 3);
+        assert_eq!(cycle(&[1, 2, 3], &7, false), 1);
+
+        let mut config = Config::default();
+        let rows = rows();
+        let Some(Row::Setting(words)) = rows.iter().find(|r| r.name() == "Words") else {
+            panic!("no words setting");
+        };
+
+        (words.step)(&mut config, true);
+        assert_eq!((words.value)(&config), "10");
+        (words.step)(&mut config, true);
+        assert_eq!((words.value)(&config), "25");
+
+        let Some(Row::Setting(mode)) = rows.iter().find(|r| r.name() == "Mode") else {
+            panic!("no mode setting");
+        };
+        (words.step)(&mut config, true);
+        (mode.step)(&mut config, true);
+        assert_eq!((mode.value)(&config), Mode::default().get_name());
+    }
+}
@@ -8,6 +8,7 @@ pub struct Theme {
     pub text: Rc<RefCell<Color>>,
     pub error: Rc<RefCell<Color>>,
     pub ghost: Rc<RefCell<Color>>,
+    pub caret: Rc<RefCell<Color>>,
 }
 
 #[derive(PartialEq, Hash, Eq, Debug, Clone, Serialize, Deserialize, Default)]
@@ -27,21 +28,23 @@ impl Theme {
             text: Rc::new(RefCell::new(Color::new(1.0, 1.0, 1.0, 1.0))),
             error: Rc::new(RefCell::new(Color::new(1.0, 0.0, 0.0, 1.0))),
             ghost: Rc::new(RefCell::new(Color::new(1.0, 1.0, 1.0, 0.5))),
+            caret: Rc::new(RefCell::new(Color::new(1.0, 1.0, 1.0, 1.0))),
         }
     }
 
     pub fn set(&self, theme_name: &ThemeName) {
-        let (bg, text, error, ghost) = match theme_name {
-            ThemeName::Atom => (0x161719, 0xc5c8c6, 0xfd5ff1, 0x444444),
-            ThemeName::Gruvbox => (0x1b1b1b, 0xebdbb2, 0xcc241d, 0x665c54),
-            ThemeName::Catppuccin => (0x1e1e2e, 0xcdd6f4, 0xf38ba8, 0x585b70),
-            ThemeName::Tokyonight => (0x1a1b26, 0xc0caf5, 0xf7768e, 0x33467c),
+        let (bg, text, error, ghost, caret) = match theme_name {
+            ThemeName::Atom => (0x161719, 0xc5c8c6, 0xfd5ff1, 0x444444, 0x528bff),
+            ThemeName::Gruvbox => (0x1b1b1b, 0xebdbb2, 0xcc241d, 0x665c54, 0xfabd2f),
+            ThemeName::Catppuccin => (0x1e1e2e, 0xcdd6f4, 0xf38ba8, 0x585b70, 0xf5e0dc),
+            ThemeName::Tokyonight => (0x1a1b26, 0xc0caf5, 0xf7768e, 0x33467c, 0x7aa2f7),
         };
 
         *self.bg.borrow_mut() = Color::from_hex(bg);
         *self.text.borrow_mut() = Color::from_hex(text);
         *self.error.borrow_mut() = Color::from_hex(error);
         *self.ghost.borrow_mut() = Color::from_hex(ghost);
+        *self.caret.borrow_mut() = Color::from_hex(caret);
     }
 
     pub fn get_theme(theme_name: &ThemeName) -> Self {
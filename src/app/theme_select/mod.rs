@@ -1,160 +1,127 @@
 use std::rc::Rc;
 
 use macroquad::input::{KeyCode, MouseButton};
-use macroquad::text::TextDimensions;
-use macroquad::{input, text, window};
+use macroquad::{input, window};
 
-use super::style::BorderParams;
-use super::theme::ThemeName::*;
-use super::{util, App, Screen, Value};
-
-mod button;
-mod cancel_button;
+use super::events::Event;
+use super::focus::{Focus, Nav, ThemeSelectFocus::*};
+use super::theme::{Theme as Colors, ThemeName::*};
+use super::widget::Widget;
+use super::{App, Screen, Style};
 
 pub async fn run(app: &mut App) {
     let current = app.config.theme.clone();
 
-    let mut focus = -1;
+    app.events.drain();
 
     let themes = [Atom, Catppuccin, Gruvbox, Tokyonight];
-    let mut buttons = themes.map(|t| button::Button::new(t, &app.style, Rc::clone(&app.font)));
-
-    let cancel_button = cancel_button::CancelButton::new(&app.style, Rc::clone(&app.font));
+    // each theme is shown in its own colors
+    let mut buttons = Widget::row(
+        &app.style,
+        1.0,
+        themes
+            .iter()
+            .enumerate()
+            .map(|(i, theme)| {
+                let style = Style {
+                    theme: Colors::get_theme(theme),
+                    font_size: Rc::clone(&app.style.font_size),
+                    ..Style::default()
+                };
+                Widget::button(
+                    Theme(i),
+                    &format!("{:?}", theme),
+                    &style,
+                    Rc::clone(&app.font),
+                )
+                .filled()
+            })
+            .collect(),
+    );
+    let mut cancel_button = Widget::button(
+        CancelButton,
+        "Cancel (ESC)",
+        &app.style,
+        Rc::clone(&app.font),
+    );
+
+    let mut focus = Focus::new(
+        (0..themes.len()).map(Theme).chain([CancelButton]).collect(),
+        Some(CancelButton),
+    )
+    .with_arrows();
 
     // to deal with holding
     let mut is_mouse_held = true;
 
     loop {
-        if let Some(k) = input::get_last_key_pressed() {
-            match k {
-                KeyCode::Equal
-                    if (input::is_key_down(KeyCode::LeftSuper)
-                        || input::is_key_down(KeyCode::RightSuper)) =>
-                {
-                    input::clear_input_queue();
-                    *app.style.font_size.borrow_mut() += 5.0;
-                }
-                KeyCode::Minus
-                    if (input::is_key_down(KeyCode::LeftSuper)
-                        || input::is_key_down(KeyCode::RightSuper)) =>
-                {
-                    input::clear_input_queue();
-                    *app.style.font_size.borrow_mut() -= 5.0;
-                }
-                KeyCode::Key0
-                    if (input::is_key_down(KeyCode::LeftSuper)
-                        || input::is_key_down(KeyCode::RightSuper)) =>
-                {
-                    input::clear_input_queue();
-                    *app.style.font_size.borrow_mut() = app.config.font_size;
-                }
-                KeyCode::Tab => {
-                    if input::is_key_down(KeyCode::LeftShift)
-                        || input::is_key_down(KeyCode::RightShift)
-                    {
-                        // cycle to -1 as well
-                        if focus == -1 {
-                            focus = buttons.len() as i32 - 1;
-                        } else {
-                            focus -= 1;
-                        }
-                    } else if focus == buttons.len() as i32 - 1 {
-                        focus = -1;
+        let events = app.read_events();
+        if app.state.screen != Screen::ThemeSelect {
+            app.style.theme.set(&current);
+            return;
+        }
+        for (event, _) in events {
+            match focus.handle(event, &app.config.keybindings) {
+                Nav::Moved => continue,
+                Nav::Activated(id) => {
+                    if let Theme(i) = id {
+                        app.config.theme = themes[i].clone();
+                        app.config.update_file();
                     } else {
-                        focus = (focus + 1) % buttons.len() as i32;
+                        app.style.theme.set(&current);
                     }
+                    app.state.screen = Screen::TypingTest;
+                    return;
                 }
-                KeyCode::Escape => {
+                Nav::Ignored => (),
+            }
+
+            match event {
+                Event::Key(KeyCode::Escape | KeyCode::Enter, _) => {
                     app.style.theme.set(&current);
                     app.state.screen = Screen::TypingTest;
                     return;
                 }
-                _ => {
-                    if let Some(c) = input::get_char_pressed() {
-                        // enter
-                        if c == '\u{000d}' {
-                            if focus >= 0 {
-                                app.config.theme = buttons[focus as usize].theme_name.clone();
-                                app.config.update_file();
-                            }
-                            app.state.screen = Screen::TypingTest;
-                            return;
-                        }
+                Event::Key(key, mods) => {
+                    if let Some(action) = app.config.keybindings.action(key, mods) {
+                        app.font_action(action);
                     }
                 }
+                _ => (),
             }
         }
 
         window::clear_background(*app.style.theme.bg.borrow());
 
-        let mut x = 0.25 * window::screen_width();
-        let mut y = 0.24 * window::screen_height();
-
-        for (i, button) in buttons.iter_mut().enumerate() {
-            let TextDimensions { width, .. } = text::measure_text(
-                &button.text,
-                Some(&app.font),
-                *button.style.font_size.borrow() as u16,
-                1.0,
-            );
-
-            if x + width > 0.75 * window::screen_width() {
-                y += *button.style.font_size.borrow() + 30.0;
-                x = 0.25 * window::screen_width();
-            }
-
-            button.style.x = Value::Absolute(x);
-            button.style.y = Value::Absolute(y);
+        buttons.center(0.24 * window::screen_height());
+        buttons.draw(focus.get());
 
-            if i as i32 == focus {
-                focus = i as i32;
-                *button.style.border.as_mut().unwrap() = BorderParams {
-                    size: 2.0,
-                    color: Rc::clone(&button.style.theme.text),
-                }
-            } else {
-                *button.style.border.as_mut().unwrap() = BorderParams {
-                    size: 2.0,
-                    color: Rc::clone(&button.style.theme.ghost),
-                }
-            }
-
-            button.update();
-            x += width + *app.style.font_size.borrow();
-        }
-
-        cancel_button.update();
-
-        if util::is_hover(&cancel_button.style) || focus == -1 {
-            focus = -1;
-            cancel_button.style.draw_border();
-            app.style.theme.set(&current);
-        }
+        cancel_button.center((window::screen_height() + *app.style.font_size.borrow()) / 2.0);
+        cancel_button.draw(focus.get());
+        app.palette.update();
 
         window::next_frame().await;
 
-        for (i, button) in buttons.iter().enumerate() {
-            if util::is_hover(&button.style) {
-                focus = i as i32;
-            }
-        }
+        let hovered = buttons.hovered().or_else(|| cancel_button.hovered());
+        focus.mouse(hovered);
 
-        if focus >= 0 {
-            app.style.theme.set(&buttons[focus as usize].theme_name);
+        // the focused theme is previewed
+        match focus.get() {
+            Some(Theme(i)) => app.style.theme.set(&themes[i]),
+            _ => app.style.theme.set(&current),
         }
 
+        // a click on a theme picks it, anywhere else cancels
         if input::is_mouse_button_down(MouseButton::Left) {
             if !is_mouse_held {
-                if focus >= 0 && util::is_hover(&buttons[focus as usize].style) {
-                    app.config.theme = buttons[focus as usize].theme_name.clone();
+                if let Some(Theme(i)) = hovered {
+                    app.config.theme = themes[i].clone();
                     app.config.update_file();
-                    app.state.screen = Screen::TypingTest;
-                    return;
-                } else if focus != -2 {
+                } else {
                     app.style.theme.set(&current);
-                    app.state.screen = Screen::TypingTest;
-                    return;
                 }
+                app.state.screen = Screen::TypingTest;
+                return;
             }
             is_mouse_held = true;
         } else {
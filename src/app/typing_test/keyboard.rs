@@ -0,0 +1,153 @@
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use macroquad::text::{self, Font};
+use macroquad::{shapes, window};
+
+use crate::app::text::PrintOptions;
+use crate::app::{theme::Theme, Layout, Style, Value};
+
+const FLASH: Duration = Duration::from_millis(150);
+
+const ROW_OFFSETS: [f32; 4] = [0.0, 1.5, 1.75, 2.25];
+
+pub struct Keyboard {
+    pub style: Style,
+    font: Rc<Font>,
+    keys: Vec<Vec<char>>,
+    shifted: Vec<Vec<char>>,
+    /// The last key pressed, as (row, column) with the space bar in a row of its own,
+    /// when and whether it was wrong.
+    pressed: Option<((usize, usize), Instant, bool)>,
+}
+
+impl Keyboard {
+    pub fn new(style: &Style, layout: &Layout, font: Rc<Font>) -> Self {
+        let f1 = Rc::clone(&style.font_size);
+
+        Keyboard {
+            font,
+            keys: layout.rows(),
+            shifted: layout.shifted_rows(),
+            pressed: None,
+            style: Style {
+                y: Value::Relative(Box::new(move |_| {
+                    window::screen_height() / 2.0 + 5.0 * *f1.borrow()
+                })),
+                // the width of a key
+                width: Value::Relative(Box::new(|this| {
+                    let fsize = *this.font_size.borrow();
+                    let below = window::screen_height() - this.y() - fsize;
+
+                    (fsize * 1.6)
+                        .min(window::screen_width() * 0.8 / 15.0)
+                        .min(below / 5.0)
+                        .max(0.0)
+                })),
+                font_size: Rc::clone(&style.font_size),
+                theme: Theme {
+                    bg: Rc::clone(&style.theme.bg),
+                    ghost: Rc::clone(&style.theme.ghost),
+                    text: Rc::clone(&style.theme.text),
+                    error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
+                },
+                ..Style::default()
+            },
+        }
+    }
+
+    fn find(&self, c: char) -> Option<(usize, usize)> {
+        if c == ' ' {
+            return Some((4, 0));
+        }
+
+        [&self.keys, &self.shifted].iter().find_map(|rows| {
+            rows.iter()
+                .enumerate()
+                .find_map(|(row, keys)| keys.iter().position(|k| *k == c).map(|col| (row, col)))
+        })
+    }
+
+    pub fn press(&mut self, c: char, wrong: bool) {
+        if let Some(key) = self.find(c) {
+            self.pressed = Some((key, Instant::now(), wrong));
+        }
+    }
+
+    pub fn update(&self, next: Option<char>) {
+        let unit = self.style.width();
+        if unit <= 0.0 {
+            return;
+        }
+
+        let size = unit * 0.9;
+        let x = (window::screen_width() - 15.0 * unit) / 2.0;
+        let y = self.style.y();
+        let fsize = (*self.style.font_size.borrow()).min(unit * 0.6);
+
+        let text_color = *self.style.theme.text.borrow();
+        let ghost = *self.style.theme.ghost.borrow();
+        let error = *self.style.theme.error.borrow();
+        let bg = *self.style.theme.bg.borrow();
+
+        let next = next.and_then(|c| self.find(c));
+        let pressed = self
+            .pressed
+            .filter(|(_, at, _)| at.elapsed() < FLASH)
+            .map(|(key, _, wrong)| (key, if wrong { error } else { text_color }));
+
+        let draw_key = |key: (usize, usize), kx: f32, ky: f32, width: f32, label: &str| {
+            let lit = pressed.filter(|(k, _)| *k == key);
+
+            if let Some((_, fill)) = lit {
+                shapes::draw_rectangle(kx, ky, width, size, fill);
+            }
+            if next == Some(key) {
+                shapes::draw_rectangle_lines(kx, ky, width, size, 3.0, text_color);
+            } else {
+                shapes::draw_rectangle_lines(kx, ky, width, size, 1.0, ghost);
+            }
+
+            let label_color = match (lit, next == Some(key)) {
+                (Some(_), _) => bg,
+                (None, true) => text_color,
+                (None, false) => ghost,
+            };
+
+            let dim = text::measure_text(label, Some(&self.font), fsize as u16, 1.0);
+            crate::app::text::print_text(
+                &self.style,
+                label,
+                PrintOptions {
+                    x: Some(kx + (width - dim.width) / 2.0),
+                    y: Some(ky + (size - dim.height) / 2.0),
+                    font: Some(Rc::clone(&self.font)),
+                    font_size: Some(fsize),
+                    color: Some(label_color),
+                    ..PrintOptions::default()
+                },
+            );
+        };
+
+        for (row, keys) in self.keys.iter().enumerate() {
+            for (col, key) in keys.iter().enumerate() {
+                draw_key(
+                    (row, col),
+                    x + (ROW_OFFSETS[row] + col as f32) * unit,
+                    y + row as f32 * unit,
+                    size,
+                    &key.to_string(),
+                );
+            }
+        }
+
+        draw_key(
+            (4, 0),
+            x + 4.5 * unit,
+            y + 4.0 * unit,
+            6.0 * unit - (unit - size),
+            "",
+        );
+    }
+}
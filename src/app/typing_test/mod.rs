@@ -1,28 +1,36 @@
+use std::process;
 use std::rc::Rc;
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use macroquad::input::{self, KeyCode, MouseButton};
-use macroquad::math::Vec2;
 use macroquad::window;
 
-use crate::app::focus::{Focus, TypingTestFocus::*};
-use crate::app::util;
+use crate::app::events::Event;
+use crate::app::focus::{
+    Focus, Nav,
+    TypingTestFocus::{self, *},
+};
+use crate::app::widget::Widget;
+use crate::{Resume, RunResult};
 
+mod keyboard;
 mod mode_select;
-mod next_button;
-mod restart_button;
 mod textbox;
-mod theme_button;
 mod tracker;
 
-use super::{App, Screen};
+use super::{Action, App, Mode, Screen, Sprint};
 
 pub async fn run(app: &mut App) {
     input::clear_input_queue();
+    app.events.drain();
     app.state.max_wpm = 0.0;
     app.state.incremental_wpm.clear();
 
-    let mut focus = Nothing;
+    let mut focus = Focus::new(
+        vec![RestartButton, NextButton, ThemeButton, SearchButton],
+        None,
+    )
+    .with_typing(vec![TypingBox]);
 
     let mut typingbox = textbox::TextBox::new(
         &app.style,
@@ -30,148 +38,207 @@ pub async fn run(app: &mut App) {
         Rc::clone(&app.typing_font),
     );
 
+    typingbox.zen = matches!(app.state.mode, Mode::Zen { .. });
+    typingbox.caret = app.config.caret.clone();
+
+    // a sprint training is over once another mode is picked
+    if !matches!(app.state.mode, Mode::Sprint { .. }) {
+        app.state.sprints.clear();
+    }
+    let sprint = Duration::from_secs(app.config.sprint.seconds);
+
     let tracker = tracker::Tracker::new(&app.style, Rc::clone(&app.font));
-    let next_button = next_button::NextButton::new(&app.style, Rc::clone(&app.font));
-    let restart_button = restart_button::RestartButton::new(&app.style, Rc::clone(&app.font));
-    let theme_button = theme_button::ThemeButton::new(&app.style, Rc::clone(&app.font));
+    let button = |id, text| Widget::button(id, text, &app.style, Rc::clone(&app.font));
+    let mut buttons = Widget::row(
+        &app.style,
+        1.0,
+        vec![button(NextButton, "Next"), button(RestartButton, "Restart")],
+    );
+    let mut theme_button = button(ThemeButton, "Theme");
+    let mut search_button = button(SearchButton, "Search");
     let mut mode_select = mode_select::ModeSelect::new(&app.style, Rc::clone(&app.font));
+    let mut keyboard = app.config.keyboard.show.then(|| {
+        keyboard::Keyboard::new(
+            &app.style,
+            &app.config.keyboard.layout(),
+            Rc::clone(&app.typing_font),
+        )
+    });
 
     let mut interval = Instant::now();
     let mut wpm = 0.0;
+    let mut skip_space = false;
 
-    loop {
-        if let Some(k) = input::get_last_key_pressed() {
-            match k {
-                KeyCode::Backspace => {
-                    input::clear_input_queue();
-                    focus = TypingBox;
-                    typingbox.delete_char();
-                }
-                KeyCode::Equal
-                    if (input::is_key_down(KeyCode::LeftSuper)
-                        || input::is_key_down(KeyCode::RightSuper)) =>
-                {
-                    input::clear_input_queue();
-                    *app.style.font_size.borrow_mut() += 5.0;
-                }
-                KeyCode::Minus
-                    if (input::is_key_down(KeyCode::LeftSuper)
-                        || input::is_key_down(KeyCode::RightSuper)) =>
-                {
-                    input::clear_input_queue();
-                    *app.style.font_size.borrow_mut() -= 5.0;
+    if app.config.resume {
+        if let Some(resume) = Resume::load(&app.config.get_resume_path()) {
+            if resume.text == app.state.mode.get_inner() {
+                typingbox.replay(&resume.typed, Duration::from_millis(resume.elapsed));
+
+                for (t, w) in resume.samples {
+                    app.state.add_wpm(Duration::from_millis(t), w);
                 }
-                KeyCode::Key0
-                    if (input::is_key_down(KeyCode::LeftSuper)
-                        || input::is_key_down(KeyCode::RightSuper)) =>
-                {
-                    input::clear_input_queue();
-                    *app.style.font_size.borrow_mut() = app.config.font_size;
+                wpm = app.state.incremental_wpm.last().map_or(0.0, |(_, w)| *w);
+            }
+        }
+    }
+
+    loop {
+        let fsize = *app.style.font_size.borrow();
+        let middle = window::screen_height() / 2.0;
+        buttons.center(middle + 3.0 * fsize);
+        theme_button.center(middle - 5.0 * fsize);
+        search_button.center(middle + 6.0 * fsize);
+
+        for (event, at) in app.read_events() {
+            // the character of a Space that pressed a button isn't typed
+            if std::mem::take(&mut skip_space) && event == Event::Char(' ') {
+                continue;
+            }
+
+            match focus.handle(event, &app.config.keybindings) {
+                Nav::Moved => continue,
+                Nav::Activated(id) => {
+                    if press(app, &mut typingbox, id) {
+                        return;
+                    }
+                    wpm = 0.0;
+                    skip_space = matches!(event, Event::Key(KeyCode::Space, _));
+                    continue;
                 }
-                KeyCode::Enter => {
-                    input::clear_input_queue();
-                    match focus {
-                        NextButton => {
-                            app.state.mode.next(&app.data);
-                            typingbox.refresh(app.state.mode.get_inner());
-                            wpm = 0.0;
-                            app.state.incremental_wpm.clear();
-                            app.state.max_wpm = 0.0;
-                        }
-                        RestartButton => {
-                            typingbox.refresh(app.state.mode.get_inner());
-                            wpm = 0.0;
-                            app.state.incremental_wpm.clear();
-                            app.state.max_wpm = 0.0;
-                        }
-                        ThemeButton => {
-                            app.state.screen = Screen::ThemeSelect;
-                            return;
-                        }
-                        _ => (),
+                Nav::Ignored => (),
+            }
+
+            let action = match event {
+                Event::Key(key, mods) => app.config.keybindings.action_while_typing(key, mods),
+                Event::Action(action) => Some(action),
+                Event::Char(_) => None,
+            };
+            if let Some(action) = action {
+                match action {
+                    Action::Restart => {
+                        restart(app, &mut typingbox);
+                        wpm = 0.0;
                     }
+                    Action::Next => {
+                        app.next_text();
+                        restart(app, &mut typingbox);
+                        wpm = 0.0;
+                    }
+                    Action::Quit => process::exit(0),
+                    Action::Theme => {
+                        app.state.screen = Screen::ThemeSelect;
+                        return;
+                    }
+                    Action::Settings => {
+                        app.state.screen = Screen::Settings;
+                        return;
+                    }
+                    Action::Search => {
+                        app.state.screen = Screen::QuoteSearch;
+                        return;
+                    }
+                    Action::Lessons => {
+                        app.state.screen = Screen::Lessons;
+                        return;
+                    }
+                    Action::Session => {
+                        app.start_session();
+                        restart(app, &mut typingbox);
+                        wpm = 0.0;
+                    }
+                    _ => app.font_action(action),
                 }
-                KeyCode::Tab => {
-                    input::clear_input_queue();
-                    focus.next();
+                continue;
+            }
+
+            match event {
+                Event::Key(KeyCode::Backspace, _) => {
+                    focus.set(Some(TypingBox));
+                    typingbox.delete_char();
+                }
+                Event::Key(KeyCode::Enter, mods) if typingbox.zen && mods.shift => {
+                    finish(app, &typingbox, None);
+                    return;
                 }
+                Event::Key(..) | Event::Action(_) => (),
                 // this passes the keytrokes to type
-                _ => {
-                    if let Some(c) = input::get_char_pressed() {
-                        focus = TypingBox;
-                        if typingbox.on_type(c) {
-                            app.state.wpm = typingbox.get_wpm();
-                            app.state.accuracy = typingbox.get_accuracey();
-                            app.state.time = typingbox.state.time_started.elapsed();
-
-                            wpm = typingbox.get_wpm();
-                            app.state
-                                .add_wpm(typingbox.state.time_started.elapsed(), wpm);
-
-                            app.state.screen = Screen::End;
-                            return;
-                        }
+                Event::Char(c) => {
+                    focus.set(Some(TypingBox));
+                    if let Some(keyboard) = &mut keyboard {
+                        keyboard.press(c, typingbox.next_char().is_some_and(|n| n != c));
+                    }
+                    let done = typingbox.on_type(c);
+
+                    // the clock starts at the first key rather than the frame it's drawn
+                    if !typingbox.state.started && typingbox.state.char_index > 0 {
+                        typingbox.state.started = true;
+                        typingbox.state.time_started = at;
+                    }
+                    if done {
+                        finish(app, &typingbox, None);
+                        return;
+                    }
+
+                    // anything typed after the mistake is dropped along with it
+                    if app.config.auto_restart_on_error && typingbox.state.mistakes > 0 {
+                        restart(app, &mut typingbox);
+                        wpm = 0.0;
+                        break;
                     }
                 }
             }
         }
 
-        if focus == TypingBox {
+        let elapsed = if typingbox.state.started {
+            typingbox.state.time_started.elapsed()
+        } else {
+            Duration::ZERO
+        };
+        if let Some(reason) = app.config.fail.check(
+            typingbox.get_wpm(),
+            typingbox.get_accuracey(),
+            typingbox.state.mistakes,
+            elapsed,
+        ) {
+            finish(app, &typingbox, Some(reason));
+            return;
+        }
+        if matches!(app.state.mode, Mode::Sprint { .. }) && elapsed >= sprint {
+            finish(app, &typingbox, None);
+            return;
+        }
+
+        if focus.is(TypingBox) {
             input::show_mouse(false);
         } else {
             input::show_mouse(true);
         }
 
+        let hovered = buttons
+            .hovered()
+            .or_else(|| theme_button.hovered())
+            .or_else(|| search_button.hovered());
+        if let Some(id) = focus.mouse(hovered) {
+            if press(app, &mut typingbox, id) {
+                return;
+            }
+            wpm = 0.0;
+        }
+
         if input::is_mouse_button_pressed(MouseButton::Left) {
-            match focus {
-                NextButton => {
-                    app.state.mode.next(&app.data);
-                    typingbox.refresh(app.state.mode.get_inner());
-                    wpm = 0.0;
-                    app.state.incremental_wpm.clear();
-                    app.state.max_wpm = 0.0;
-                }
-                RestartButton => {
-                    typingbox.refresh(app.state.mode.get_inner());
-                    wpm = 0.0;
-                    app.state.incremental_wpm.clear();
-                    app.state.max_wpm = 0.0;
-                }
-                ThemeButton => {
-                    app.state.screen = Screen::ThemeSelect;
+            if let Some(mode) = &mode_select.next_mode_selected {
+                // lessons are picked on their own screen
+                if let Mode::Lesson { .. } = mode {
+                    app.state.screen = Screen::Lessons;
                     return;
                 }
-                _ => (),
-            }
 
-            if let Some(mode) = &mode_select.next_mode_selected {
                 if *mode != app.state.mode {
-                    app.state.mode = mode.clone();
-                    app.state.mode.next(&app.data);
-                    typingbox.refresh(app.state.mode.get_inner());
+                    app.set_mode(mode);
+                    restart(app, &mut typingbox);
                     wpm = 0.0;
-                    app.state.incremental_wpm.clear();
-                    app.state.max_wpm = 0.0;
-
-                    app.config.mode = app.state.mode.clone();
-                    app.config.update_file();
-                }
-            }
-        }
-
-        match input::mouse_delta_position() {
-            Vec2 { x: dx, y: dy } if dx != 0.0 && dy != 0.0 => {
-                focus = if util::is_hover(&restart_button.style) {
-                    RestartButton
-                } else if util::is_hover(&theme_button.style) {
-                    ThemeButton
-                } else if util::is_hover(&next_button.style) {
-                    NextButton
-                } else {
-                    Nothing
                 }
             }
-            _ => (),
         }
 
         if typingbox.state.started
@@ -182,33 +249,131 @@ pub async fn run(app: &mut App) {
             interval = Instant::now();
             app.state
                 .add_wpm(typingbox.state.time_started.elapsed(), wpm);
+
+            if app.config.resume {
+                Resume {
+                    text: app.state.mode.get_inner(),
+                    typed: typingbox.typed(),
+                    elapsed: typingbox.state.time_started.elapsed().as_millis() as u64,
+                    samples: app
+                        .state
+                        .incremental_wpm
+                        .iter()
+                        .map(|(t, w)| (t.as_millis() as u64, *w))
+                        .collect(),
+                }
+                .save(&app.config.get_resume_path());
+            }
         }
 
         window::clear_background(*app.style.theme.bg.borrow());
 
         typingbox.update();
+        if let Some(keyboard) = &keyboard {
+            keyboard.update(typingbox.next_char());
+        }
         tracker.update(
             &typingbox.style,
             typingbox.state.word_index,
-            typingbox.state.words.len(),
+            (!typingbox.zen).then_some(typingbox.state.words.len()),
+            matches!(app.state.mode, Mode::Sprint { .. }).then(|| sprint.saturating_sub(elapsed)),
             wpm,
         );
 
-        if focus != TypingBox {
-            next_button.update();
-            restart_button.update();
-            theme_button.update();
+        if !focus.is(TypingBox) {
+            buttons.draw(focus.get());
+            theme_button.draw(focus.get());
+            search_button.draw(focus.get());
 
             mode_select.update(&app.state.mode);
         }
 
-        match focus {
-            ThemeButton => theme_button.style.draw_border(),
-            RestartButton => restart_button.style.draw_border(),
-            NextButton => next_button.style.draw_border(),
-            _ => (),
-        }
+        app.palette.update();
 
         window::next_frame().await;
     }
 }
+
+fn press(app: &mut App, typingbox: &mut textbox::TextBox, id: TypingTestFocus) -> bool {
+    match id {
+        NextButton => {
+            app.next_text();
+            restart(app, typingbox);
+        }
+        RestartButton => restart(app, typingbox),
+        ThemeButton => {
+            app.state.screen = Screen::ThemeSelect;
+            return true;
+        }
+        SearchButton => {
+            app.state.screen = Screen::QuoteSearch;
+            return true;
+        }
+        TypingBox => (),
+    }
+
+    false
+}
+
+fn restart(app: &mut App, typingbox: &mut textbox::TextBox) {
+    typingbox.refresh(app.state.mode.get_inner());
+    typingbox.zen = matches!(app.state.mode, Mode::Zen { .. });
+    if !matches!(app.state.mode, Mode::Sprint { .. }) {
+        app.state.sprints.clear();
+    }
+    app.state.incremental_wpm.clear();
+    app.state.max_wpm = 0.0;
+
+    Resume::clear(&app.config.get_resume_path());
+}
+
+fn finish(app: &mut App, typingbox: &textbox::TextBox, failed: Option<String>) {
+    let time = if typingbox.state.started {
+        typingbox.state.time_started.elapsed()
+    } else {
+        Duration::ZERO
+    };
+
+    app.state.wpm = typingbox.get_wpm();
+    app.state.accuracy = typingbox.get_accuracey();
+    app.state.time = time;
+    app.state.add_wpm(time, app.state.wpm);
+
+    app.state.missed = typingbox.missed_words(app.config.practice.slowest);
+
+    Resume::clear(&app.config.get_resume_path());
+
+    let run = RunResult {
+        mode: app.state.mode.to_string(),
+        wpm: app.state.wpm,
+        accuracy: app.state.accuracy,
+        time: time.as_millis() as u64,
+        finished: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+        failed,
+    };
+    run.append(&app.config.get_history_path());
+    if let Some(session) = &mut app.state.session {
+        // practicing the missed words isn't one of the tests of the session
+        if !matches!(app.state.mode, Mode::Missed { .. }) {
+            session.runs.push(run.clone());
+        }
+    }
+    app.state.history.push(run);
+
+    if let Mode::Sprint { .. } = app.state.mode {
+        app.state.sprints.push(Sprint {
+            samples: app.state.incremental_wpm.clone(),
+            wpm: app.state.wpm,
+            accuracy: app.state.accuracy,
+        });
+
+        if app.state.sprints.len() < app.config.sprint.rounds {
+            app.state.screen = Screen::Rest;
+            return;
+        }
+    }
+
+    app.state.screen = Screen::End;
+}
@@ -1,210 +1,97 @@
 use std::rc::Rc;
 
-use macroquad::color::Color;
-use macroquad::text::{self, Font};
-use macroquad::window;
+use macroquad::text::Font;
 
 use crate::app::style::Style;
-use crate::app::text::PrintOptions;
-use crate::app::theme::Theme;
-use crate::app::{self, util, BorderParams, Mode, Value};
+use crate::app::widget::Widget;
+use crate::app::Mode;
 use crate::data_provider::Quote;
 
+const TOP: f32 = 10.0;
+
 pub struct ModeSelect {
-    style: Style,
-    buttons: Vec<Button>,
-    word_buttons: Vec<Button>,
+    modes: Vec<Mode>,
+    buttons: Widget<usize>,
+    word_buttons: Widget<usize>,
     pub next_mode_selected: Option<Mode>,
 }
 
 impl ModeSelect {
     pub fn new(style: &Style, font: Rc<Font>) -> Self {
+        let s = String::new;
+        let modes = [
+            ("Words", Mode::Words { n: 10, s: s() }),
+            (
+                "Quote",
+                Mode::Quote(Quote {
+                    source: s(),
+                    quote: s(),
+                }),
+            ),
+            ("Daily", Mode::Daily { day: 0, s: s() }),
+            ("Zen", Mode::Zen { s: s() }),
+            (
+                "Drill",
+                Mode::Drill {
+                    ngram: s(),
+                    repetitions: 0,
+                    s: s(),
+                },
+            ),
+            ("Lesson", Mode::Lesson { keys: s(), s: s() }),
+            ("Sprint", Mode::Sprint { s: s() }),
+        ];
+        let words = [10, 30, 50, 100].map(|n| (n.to_string(), Mode::Words { n, s: s() }));
+
+        let button = |id, text: &str| Widget::button(id, text, style, Rc::clone(&font)).scaled(0.7);
+        let buttons = Widget::row(
+            style,
+            0.0,
+            modes
+                .iter()
+                .enumerate()
+                .map(|(i, (text, _))| button(i, text))
+                .collect(),
+        );
+        let word_buttons = Widget::row(
+            style,
+            0.0,
+            words
+                .iter()
+                .enumerate()
+                .map(|(i, (text, _))| button(modes.len() + i, text))
+                .collect(),
+        );
+
         ModeSelect {
+            modes: modes
+                .into_iter()
+                .map(|(_, m)| m)
+                .chain(words.into_iter().map(|(_, m)| m))
+                .collect(),
+            buttons,
+            word_buttons,
             next_mode_selected: None,
-            style: Style {
-                y: Value::Absolute(10.0),
-                font_size: Rc::clone(&style.font_size),
-                ..Style::default()
-            },
-            buttons: [
-                (
-                    "Words",
-                    Mode::Words {
-                        n: 10,
-                        s: "".to_string(),
-                    },
-                ),
-                (
-                    "Quote",
-                    Mode::Quote(Quote {
-                        source: "".to_string(),
-                        quote: "".to_string(),
-                    }),
-                ),
-            ]
-            .into_iter()
-            .map(|(s, m)| Button::new(s.to_string(), m, style, Rc::clone(&font)))
-            .collect(),
-            word_buttons: [
-                (
-                    "10",
-                    Mode::Words {
-                        n: 10,
-                        s: "".to_string(),
-                    },
-                ),
-                (
-                    "30",
-                    Mode::Words {
-                        n: 30,
-                        s: "".to_string(),
-                    },
-                ),
-                (
-                    "50",
-                    Mode::Words {
-                        n: 50,
-                        s: "".to_string(),
-                    },
-                ),
-                (
-                    "100",
-                    Mode::Words {
-                        n: 100,
-                        s: "".to_string(),
-                    },
-                ),
-            ]
-            .into_iter()
-            .map(|(s, m)| Button::new(s.to_string(), m, style, Rc::clone(&font)))
-            .collect(),
         }
     }
 
     pub fn update(&mut self, mode: &Mode) {
-        let text = mode.get_name();
-
-        let mut hover_mode = None;
-
-        let total_button_width: f32 = self.buttons.iter().map(|b| b.style.width()).sum();
-
-        let mut x = 0.0;
-        for btn in &mut self.buttons {
-            let color = if text == btn.text {
-                *btn.style.theme.text.borrow()
-            } else {
-                *btn.style.theme.ghost.borrow()
-            };
-
-            btn.update(
-                x + (window::screen_width() - total_button_width) / 2.0,
-                self.style.y(),
-                color,
-            );
-
-            x += btn.style.width();
-
-            if util::is_hover(&btn.style) {
-                btn.style.draw_border();
-
-                hover_mode = Some(btn.mode.clone());
-            }
-        }
-
-        let y = self.buttons[0].style.height() + self.style.y();
-
-        if let Mode::Words { n, .. } = mode.clone() {
-            let mut x = 0.0;
-
-            let total_button_width: f32 = self.word_buttons.iter().map(|b| b.style.width()).sum();
-
-            for btn in &mut self.word_buttons {
-                let color = if n == btn.text.parse::<usize>().unwrap() {
-                    *btn.style.theme.text.borrow()
-                } else {
-                    *btn.style.theme.ghost.borrow()
-                };
-
-                btn.update(
-                    x + (window::screen_width() - total_button_width) / 2.0,
-                    y,
-                    color,
-                );
-
-                x += btn.style.width();
-
-                if util::is_hover(&btn.style) {
-                    btn.style.draw_border();
-
-                    hover_mode = Some(btn.mode.clone());
-                }
-            }
-        }
-
-        self.next_mode_selected = hover_mode;
-    }
-}
-
-struct Button {
-    text: String,
-    style: Style,
-    font: Rc<Font>,
-    mode: Mode,
-}
-
-impl Button {
-    pub fn new(text: String, mode: Mode, style: &Style, font: Rc<Font>) -> Self {
-        let f1 = Rc::clone(&style.font_size);
-        let font1 = Rc::clone(&font);
-
-        let f2 = Rc::clone(&style.font_size);
-        let font2 = Rc::clone(&font);
-        let t2 = text.clone();
-
-        Button {
-            mode,
-            text: text.clone(),
-            style: Style {
-                border: Some(BorderParams {
-                    size: 2.0,
-                    color: Rc::clone(&style.theme.text),
-                }),
-                theme: Theme {
-                    bg: Rc::clone(&style.theme.bg),
-                    ghost: Rc::clone(&style.theme.ghost),
-                    text: Rc::clone(&style.theme.text),
-                    error: Rc::clone(&style.theme.error),
-                },
-                padding_x: Some(Value::Absolute(10.0)),
-                padding_y: Some(Value::Absolute(10.0)),
-                width: Value::Relative(Box::new(move |this| {
-                    2.0 * this.padding_x()
-                        + text::measure_text(&text, Some(&font1), *f1.borrow() as u16, 0.7).width
-                })),
-                height: Value::Relative(Box::new(move |this| {
-                    2.0 * this.padding_y()
-                        + text::measure_text(&t2, Some(&font2), *f2.borrow() as u16, 0.7).height
-                })),
-                font_size: Rc::clone(&style.font_size),
-                ..Style::default()
-            },
-            font,
+        let modes = &self.modes;
+
+        self.buttons.center(TOP);
+        self.buttons
+            .select(&|i| modes[i].get_name() == mode.get_name());
+        let mut hovered = self.buttons.hovered();
+        self.buttons.draw(hovered);
+
+        if let Mode::Words { n, .. } = mode {
+            self.word_buttons.center(TOP + self.buttons.size().y);
+            self.word_buttons
+                .select(&|i| matches!(modes[i], Mode::Words { n: m, .. } if m == *n));
+            hovered = hovered.or_else(|| self.word_buttons.hovered());
+            self.word_buttons.draw(hovered);
         }
-    }
 
-    pub fn update(&mut self, x: f32, y: f32, color: Color) {
-        self.style.x = Value::Absolute(x);
-        self.style.y = Value::Absolute(y);
-
-        app::text::print_text(
-            &self.style,
-            &self.text,
-            PrintOptions {
-                font: Some(Rc::clone(&self.font)),
-                font_scale: Some(0.7),
-                color: Some(color),
-                ..PrintOptions::default()
-            },
-        );
+        self.next_mode_selected = hovered.map(|i| modes[i].clone());
     }
 }
@@ -1,12 +1,14 @@
 use std::cmp::{max, Ordering};
 use std::rc::Rc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+use macroquad::math::Rect;
 use macroquad::text::{Font, TextDimensions, TextParams};
-use macroquad::{shapes, text, window};
+use macroquad::{shapes, text, time, window};
 
-use crate::app::{theme::Theme, BorderParams, Letter, Style, Value};
-use crate::app::{util, Word};
+use crate::app::caret::Motion;
+use crate::app::{theme::Theme, BorderParams, Caret, Letter, Style, Value};
+use crate::app::{util, Word, LINE_LENGTH};
 
 pub struct TextBoxState {
     pub words: Vec<Word>,
@@ -16,12 +18,19 @@ pub struct TextBoxState {
     pub started: bool,
     pub wrongs: usize,
     pub char_typed: i32,
+    /// When the last word was done, the words are timed from there.
+    pub last_submit: Option<Instant>,
+    /// Wrong letters and words left unfinished, not undone by deleting them.
+    pub mistakes: usize,
 }
 
 pub struct TextBox {
     pub style: Style,
     pub state: TextBoxState,
     pub font: Rc<Font>,
+    pub zen: bool,
+    pub caret: Caret,
+    motion: Motion,
 }
 
 impl TextBox {
@@ -45,6 +54,9 @@ impl TextBox {
 
         TextBox {
             font,
+            zen: false,
+            caret: Caret::default(),
+            motion: Motion::new(),
             style: Style {
                 font_size: Rc::clone(&style.font_size),
                 border: Some(BorderParams {
@@ -56,6 +68,7 @@ impl TextBox {
                     ghost: Rc::clone(&style.theme.ghost),
                     text: Rc::clone(&style.theme.text),
                     error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
                 },
                 x: Value::Relative(Box::new(move |this| {
                     (window::screen_width() - this.width()) / 2.0
@@ -68,7 +81,7 @@ impl TextBox {
                         0.0,
                         window::screen_width() * 0.8,
                         text::measure_text("o", Some(&font1), *f3.borrow() as u16, 1.0).width
-                            * 80.0,
+                            * LINE_LENGTH as f32,
                     )
                 })),
                 height: Value::Relative(Box::new(move |_| *f2.borrow() * (3.0 + 2.0 * 0.15))),
@@ -83,6 +96,8 @@ impl TextBox {
                 words,
                 wrongs: 0,
                 char_typed: 0,
+                last_submit: None,
+                mistakes: 0,
             },
         }
     }
@@ -100,10 +115,39 @@ impl TextBox {
         self.state.started = false;
         self.state.wrongs = 0;
         self.state.char_typed = 0;
+        self.state.last_submit = None;
+        self.state.mistakes = 0;
         self.style.offset_y = None;
     }
 
+    pub fn next_char(&self) -> Option<char> {
+        if self.zen {
+            return None;
+        }
+
+        let word = self.state.words.get(self.state.word_index)?;
+        Some(
+            word.letters
+                .get(self.state.char_index)
+                .map_or(' ', |l| l.letter),
+        )
+    }
+
     pub fn on_type(&mut self, c: char) -> bool {
+        if self.zen {
+            self.type_zen(c);
+            return false;
+        }
+
+        if c == ' ' {
+            let word = &self.state.words[self.state.word_index];
+            // wrong and extra letters were counted as they were typed, so only a word
+            // left unfinished without any is a mistake of its own
+            if word.typed != word.word && word.word.starts_with(&word.typed) {
+                self.state.mistakes += 1;
+            }
+        }
+
         if self.state.word_index == self.state.words.len() - 1 && c == ' ' {
             self.state.wrongs += 1;
             return true;
@@ -114,6 +158,8 @@ impl TextBox {
             return false;
         }
 
+        self.state.words[self.state.word_index].typed.push(c);
+
         if self.state.char_index == self.state.words[self.state.word_index].letters.len() {
             // if its the end of a word allow overflow
             self.state.words[self.state.word_index]
@@ -127,6 +173,7 @@ impl TextBox {
 
             self.state.char_index += 1;
             self.state.words[self.state.word_index].last_typed = self.state.char_index;
+            self.state.mistakes += 1;
             return false;
         }
 
@@ -140,6 +187,7 @@ impl TextBox {
                 ..(self.state.words[self.state.word_index].letters[self.state.char_index]).clone()
             }
         } else {
+            self.state.mistakes += 1;
             Letter {
                 color: Rc::clone(&self.style.theme.error),
                 ..(self.state.words[self.state.word_index].letters[self.state.char_index]).clone()
@@ -162,6 +210,39 @@ impl TextBox {
         false
     }
 
+    fn type_zen(&mut self, c: char) {
+        let i = self.state.word_index;
+
+        if c == ' ' {
+            // no empty words
+            if self.state.words[i].word.is_empty() {
+                return;
+            }
+
+            self.state.char_typed += 1;
+            self.state
+                .words
+                .push(Word::from_str(&self.style, "", i + 1));
+            self.state.word_index += 1;
+            self.state.char_index = 0;
+            return;
+        }
+
+        let word = &mut self.state.words[i];
+        word.typed.push(c);
+        word.word.push(c);
+        word.letters.push(Letter {
+            letter: c,
+            color: Rc::clone(&self.style.theme.text),
+            char_id: self.state.char_index,
+            word_id: i,
+        });
+
+        self.state.char_index += 1;
+        self.state.char_typed += 1;
+        word.last_typed = self.state.char_index;
+    }
+
     fn submit_word(&mut self) {
         // check if current word is wrong
         if !self.state.words[self.state.word_index]
@@ -184,12 +265,22 @@ impl TextBox {
                 - self.state.char_index as i32,
         );
 
+        let now = Instant::now();
+        self.state.words[self.state.word_index].elapsed =
+            now - self.state.last_submit.unwrap_or(self.state.time_started);
+        self.state.last_submit = Some(now);
+
         // move to the next word
         self.state.word_index += 1;
         self.state.char_index = 0;
     }
 
     pub fn delete_char(&mut self) {
+        if self.zen {
+            self.delete_zen();
+            return;
+        }
+
         if self.state.char_index == 0 {
             // return if its the first word
             if self.state.word_index == 0 {
@@ -211,6 +302,7 @@ impl TextBox {
 
         self.state.char_index -= 1;
         self.state.words[self.state.word_index].last_typed = self.state.char_index;
+        self.state.words[self.state.word_index].typed.pop();
 
         // check if we are deleting overflow
         if self.state.char_index >= self.state.words[self.state.word_index].word.len() {
@@ -224,6 +316,85 @@ impl TextBox {
         }
     }
 
+    fn delete_zen(&mut self) {
+        if self.state.char_index == 0 {
+            if self.state.word_index == 0 {
+                return;
+            }
+
+            // the space before the word
+            self.state.words.pop();
+            self.state.word_index -= 1;
+            self.state.char_index = self.state.words[self.state.word_index].letters.len();
+            self.state.char_typed -= 1;
+            return;
+        }
+
+        let word = &mut self.state.words[self.state.word_index];
+        word.typed.pop();
+        word.word.pop();
+        word.letters.pop();
+
+        self.state.char_index -= 1;
+        self.state.char_typed -= 1;
+        word.last_typed = self.state.char_index;
+    }
+
+    pub fn typed(&self) -> Vec<String> {
+        self.state.words[..=self.state.word_index]
+            .iter()
+            .map(|w| w.typed.clone())
+            .collect()
+    }
+
+    pub fn missed_words(&self, slowest: usize) -> Vec<String> {
+        let typed = &self.state.words[..=self.state.word_index.min(self.state.words.len() - 1)];
+
+        let wrong = |w: &Word| w.is_error || w.typed != w.word && w.id == self.state.word_index;
+
+        let mut right: Vec<&Word> = typed
+            .iter()
+            .filter(|w| !wrong(w) && !w.elapsed.is_zero())
+            .collect();
+        right.sort_by(|a, b| {
+            let per_letter = |w: &Word| w.elapsed.as_secs_f32() / (w.word.len() + 1) as f32;
+            per_letter(b).total_cmp(&per_letter(a))
+        });
+
+        let mut words: Vec<String> = vec![];
+
+        for word in typed
+            .iter()
+            .filter(|w| wrong(w))
+            .chain(right.into_iter().take(slowest))
+        {
+            if !words.contains(&word.word) {
+                words.push(word.word.clone());
+            }
+        }
+
+        words
+    }
+
+    pub fn replay(&mut self, typed: &[String], elapsed: Duration) {
+        for (i, word) in typed.iter().enumerate() {
+            for c in word.chars() {
+                if self.on_type(c) {
+                    return;
+                }
+            }
+
+            if i != typed.len() - 1 && self.on_type(' ') {
+                return;
+            }
+        }
+
+        self.state.started = true;
+        self.state.time_started = Instant::now()
+            .checked_sub(elapsed)
+            .unwrap_or_else(Instant::now);
+    }
+
     fn update_position(&mut self, line_breaks: &[usize]) {
         let mut left: i32 = 0;
         let mut right: i32 = line_breaks.len() as i32 - 1;
@@ -266,13 +437,23 @@ impl TextBox {
     }
 
     pub fn get_accuracey(&self) -> i32 {
+        if self.state.char_typed <= 0 {
+            return 100;
+        }
         100 - 100 * 5 * self.state.wrongs as i32 / self.state.char_typed
     }
 
     pub fn update(&mut self) {
         self.style.draw_bg();
 
-        let line_breaks = self.print_words();
+        let (line_breaks, caret) = self.print_words();
+        if let Some(target) = caret {
+            let at = self
+                .motion
+                .step(target, time::get_frame_time(), self.caret.smooth);
+            self.caret
+                .draw(at, self.motion.idle(), *self.style.theme.caret.borrow());
+        }
         self.update_position(&line_breaks);
 
         self.style.draw_mask();
@@ -283,8 +464,9 @@ impl TextBox {
         }
     }
 
-    fn print_words(&self) -> Vec<usize> {
+    fn print_words(&self) -> (Vec<usize>, Option<Rect>) {
         let mut line_breaks = vec![];
+        let mut caret = None;
 
         let mut lines = 0;
 
@@ -321,13 +503,13 @@ impl TextBox {
             if width > self.style.width.get(&self.style) - 2.0 * p_x {
                 let y = *self.style.font_size.borrow() * lines as f32 * 1.15;
 
-                self.print_letters(
+                caret = caret.or(self.print_letters(
                     &line,
                     self.style.x.get(&self.style),
                     self.style.y.get(&self.style) + y,
                     self.state.word_index,
                     self.state.char_index,
-                );
+                ));
 
                 line_breaks.push(word.id);
                 lines += 1;
@@ -338,18 +520,25 @@ impl TextBox {
         }
 
         let y = *self.style.font_size.borrow() * lines as f32 * 1.15;
-        self.print_letters(
+        caret = caret.or(self.print_letters(
             &line,
             self.style.x.get(&self.style),
             self.style.y.get(&self.style) + y,
             self.state.word_index,
             self.state.char_index,
-        );
+        ));
 
-        line_breaks
+        (line_breaks, caret)
     }
 
-    fn print_letters(&self, line: &[&Word], x: f32, y: f32, word_index: usize, char_index: usize) {
+    fn print_letters(
+        &self,
+        line: &[&Word],
+        x: f32,
+        y: f32,
+        word_index: usize,
+        char_index: usize,
+    ) -> Option<Rect> {
         let mut letters: Vec<Letter> = vec![];
 
         for word in line {
@@ -364,11 +553,14 @@ impl TextBox {
             })
         }
 
-        letters.pop();
+        // zen keeps the space after the last word so the caret shows there
+        if !self.zen {
+            letters.pop();
+        }
 
         let mut offset_x = 0.0;
         let offset_y = text::measure_text(
-            &letters
+            letters
                 .iter()
                 .fold(String::new(), |acc, l| acc + &l.letter.to_string()),
             Some(&self.font),
@@ -377,25 +569,23 @@ impl TextBox {
         )
         .offset_y;
 
+        let mut caret = None;
         for letter in &letters {
-            offset_x += self
-                .print_letter(letter, x + offset_x, y + offset_y, word_index, char_index)
-                .width;
+            let at = self.print_letter(letter, x + offset_x, y + offset_y);
+            if letter.char_id == char_index && letter.word_id == word_index {
+                caret = Some(at);
+            }
+            offset_x += at.w;
         }
+
+        caret
     }
 
-    fn print_letter(
-        &self,
-        letter: &Letter,
-        x: f32,
-        y: f32,
-        word_index: usize,
-        char_index: usize,
-    ) -> TextDimensions {
+    fn print_letter(&self, letter: &Letter, x: f32, y: f32) -> Rect {
         let style = &self.style;
 
         let dimensions = text::measure_text(
-            &letter.letter.to_string(),
+            letter.letter.to_string(),
             Some(&self.font),
             *style.font_size.borrow() as u16,
             1.0,
@@ -425,7 +615,7 @@ impl TextBox {
         let y = y + p_y + o_y;
 
         text::draw_text_ex(
-            &letter.letter.to_string(),
+            letter.letter.to_string(),
             x,
             y,
             TextParams {
@@ -451,20 +641,7 @@ impl TextBox {
             );
         }
 
-        if letter.char_id == char_index && letter.word_id == word_index {
-            text::draw_text_ex(
-                "|",
-                x - dimensions.width / 2.0,
-                y,
-                TextParams {
-                    font: Some(&self.font),
-                    font_size: *style.font_size.borrow() as u16,
-                    color: *style.theme.text.borrow(),
-                    ..TextParams::default()
-                },
-            );
-        }
-
-        dimensions
+        let fsize = *style.font_size.borrow();
+        Rect::new(x, y - 0.8 * fsize, dimensions.width, fsize)
     }
 }
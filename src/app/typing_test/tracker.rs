@@ -1,4 +1,5 @@
 use std::rc::Rc;
+use std::time::Duration;
 
 use macroquad::text::Font;
 
@@ -22,6 +23,7 @@ impl Tracker {
                     ghost: Rc::clone(&style.theme.ghost),
                     text: Rc::clone(&style.theme.ghost),
                     error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
                 },
                 font_size: Rc::clone(&style.font_size),
                 ..Style::default()
@@ -30,10 +32,23 @@ impl Tracker {
         }
     }
 
-    pub fn update(&self, typingbox_style: &Style, index: usize, len: usize, wpm: f32) {
+    pub fn update(
+        &self,
+        typingbox_style: &Style,
+        index: usize,
+        len: Option<usize>,
+        left: Option<Duration>,
+        wpm: f32,
+    ) {
+        let progress = match (left, len) {
+            (Some(left), _) => format!("{}s {:.0}", left.as_secs_f32().ceil(), wpm),
+            (None, Some(len)) => format!("{}/{} {:.0}", index, len, wpm),
+            (None, None) => format!("{} {:.0}", index, wpm),
+        };
+
         text::print_text(
             &self.style,
-            &format!("{}/{} {:.0}", index, len, wpm),
+            &progress,
             PrintOptions {
                 x: Some(typingbox_style.x()),
                 y: Some(typingbox_style.y() + self.style.y()),
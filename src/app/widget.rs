@@ -0,0 +1,319 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use macroquad::color::Color;
+use macroquad::math::{vec2, Vec2};
+use macroquad::text::{self, Font};
+use macroquad::window;
+
+use super::style::{BorderParams, Style, Value};
+use super::text::PrintOptions;
+use super::theme::Theme;
+use super::util;
+
+const PADDING: f32 = 10.0;
+
+/// A piece of a screen that is laid out by the widgets it's in, so that a screen only
+/// places the outermost one. Buttons are told apart by an id of type `T`, usually the
+/// focus of the screen, which is what `hovered` hands back.
+pub enum Widget<T> {
+    Button(Button<T>),
+    Label(Label),
+    Row(Container<T>),
+    Column(Container<T>),
+    Stack(Container<T>),
+}
+
+pub struct Container<T> {
+    pub children: Vec<Widget<T>>,
+    /// Space between two children, in font sizes.
+    gap: f32,
+    font_size: Rc<RefCell<f32>>,
+}
+
+impl<T> Container<T> {
+    fn new(style: &Style, gap: f32, children: Vec<Widget<T>>) -> Self {
+        Container {
+            children,
+            gap,
+            font_size: Rc::clone(&style.font_size),
+        }
+    }
+
+    fn gap(&self) -> f32 {
+        self.gap * *self.font_size.borrow()
+    }
+}
+
+impl<T: Copy + PartialEq> Widget<T> {
+    pub fn button(id: T, text: &str, style: &Style, font: Rc<Font>) -> Self {
+        Widget::Button(Button::new(id, text, style, font))
+    }
+
+    pub fn label(text: &str, color: &Rc<RefCell<Color>>, style: &Style, font: Rc<Font>) -> Self {
+        Widget::Label(Label::new(text, color, style, font))
+    }
+
+    pub fn row(style: &Style, gap: f32, children: Vec<Widget<T>>) -> Self {
+        Widget::Row(Container::new(style, gap, children))
+    }
+
+    pub fn column(style: &Style, gap: f32, children: Vec<Widget<T>>) -> Self {
+        Widget::Column(Container::new(style, gap, children))
+    }
+
+    pub fn stack(style: &Style, gap: f32, children: Vec<Widget<T>>) -> Self {
+        Widget::Stack(Container::new(style, gap, children))
+    }
+
+    pub fn scaled(mut self, scale: f32) -> Self {
+        if let Widget::Button(button) = &mut self {
+            button.scale = scale;
+        }
+        self
+    }
+
+    pub fn filled(mut self) -> Self {
+        if let Widget::Button(button) = &mut self {
+            button.filled = true;
+        }
+        self
+    }
+
+    pub fn child_mut(&mut self, i: usize) -> Option<&mut Widget<T>> {
+        match self {
+            Widget::Row(c) | Widget::Column(c) | Widget::Stack(c) => c.children.get_mut(i),
+            Widget::Button(_) | Widget::Label(_) => None,
+        }
+    }
+
+    pub fn select(&mut self, is_selected: &impl Fn(T) -> bool) {
+        match self {
+            Widget::Button(button) => button.selected = is_selected(button.id),
+            Widget::Label(_) => (),
+            Widget::Row(c) | Widget::Column(c) | Widget::Stack(c) => {
+                for child in &mut c.children {
+                    child.select(is_selected);
+                }
+            }
+        }
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        match self {
+            Widget::Button(button) => button.text = text.to_string(),
+            Widget::Label(label) => label.text = text.to_string(),
+            Widget::Row(_) | Widget::Column(_) | Widget::Stack(_) => (),
+        }
+    }
+
+    pub fn size(&self) -> Vec2 {
+        match self {
+            Widget::Button(button) => button.size(),
+            Widget::Label(label) => label.size(),
+            Widget::Row(c) => {
+                let gaps = c.gap() * c.children.len().saturating_sub(1) as f32;
+                c.children
+                    .iter()
+                    .map(|w| w.size())
+                    .fold(vec2(gaps, 0.0), |acc, s| vec2(acc.x + s.x, acc.y.max(s.y)))
+            }
+            Widget::Column(c) | Widget::Stack(c) => {
+                let gaps = c.gap() * c.children.len().saturating_sub(1) as f32;
+                c.children
+                    .iter()
+                    .map(|w| w.size())
+                    .fold(vec2(0.0, gaps), |acc, s| vec2(acc.x.max(s.x), acc.y + s.y))
+            }
+        }
+    }
+
+    pub fn place(&mut self, x: f32, y: f32) {
+        let size = self.size();
+
+        match self {
+            Widget::Button(button) => button.place(x, y, size),
+            Widget::Label(label) => label.place(x, y),
+            Widget::Row(c) => {
+                let gap = c.gap();
+                let mut x = x;
+                for child in &mut c.children {
+                    let width = child.size().x;
+                    child.place(x, y);
+                    x += width + gap;
+                }
+            }
+            Widget::Column(c) => {
+                let gap = c.gap();
+                let mut y = y;
+                for child in &mut c.children {
+                    let child_size = child.size();
+                    child.place(x + (size.x - child_size.x) / 2.0, y);
+                    y += child_size.y + gap;
+                }
+            }
+            Widget::Stack(c) => {
+                let gap = c.gap();
+                let mut y = y;
+                for child in &mut c.children {
+                    let height = child.size().y;
+                    child.place(x, y);
+                    y += height + gap;
+                }
+            }
+        }
+    }
+
+    pub fn center(&mut self, y: f32) {
+        let width = self.size().x;
+        self.place((window::screen_width() - width) / 2.0, y);
+    }
+
+    pub fn draw(&self, focus: Option<T>) {
+        match self {
+            Widget::Button(button) => {
+                button.draw();
+                if focus == Some(button.id) {
+                    button.style.draw_border();
+                }
+            }
+            Widget::Label(label) => label.draw(),
+            Widget::Row(c) | Widget::Column(c) | Widget::Stack(c) => {
+                for child in &c.children {
+                    child.draw(focus);
+                }
+            }
+        }
+    }
+
+    pub fn hovered(&self) -> Option<T> {
+        match self {
+            Widget::Button(button) => util::is_hover(&button.style).then_some(button.id),
+            Widget::Label(_) => None,
+            Widget::Row(c) | Widget::Column(c) | Widget::Stack(c) => {
+                c.children.iter().find_map(|w| w.hovered())
+            }
+        }
+    }
+}
+
+pub struct Button<T> {
+    pub id: T,
+    pub text: String,
+    pub style: Style,
+    font: Rc<Font>,
+    scale: f32,
+    filled: bool,
+    selected: bool,
+}
+
+impl<T> Button<T> {
+    pub fn new(id: T, text: &str, style: &Style, font: Rc<Font>) -> Self {
+        Button {
+            id,
+            text: text.to_string(),
+            font,
+            scale: 1.0,
+            filled: false,
+            selected: false,
+            style: Style {
+                border: Some(BorderParams {
+                    size: 2.0,
+                    color: Rc::clone(&style.theme.text),
+                }),
+                font_size: Rc::clone(&style.font_size),
+                theme: Theme {
+                    bg: Rc::clone(&style.theme.bg),
+                    ghost: Rc::clone(&style.theme.ghost),
+                    text: Rc::clone(&style.theme.text),
+                    error: Rc::clone(&style.theme.error),
+                    caret: Rc::clone(&style.theme.caret),
+                },
+                padding_x: Some(Value::Absolute(PADDING)),
+                padding_y: Some(Value::Absolute(PADDING)),
+                ..Style::default()
+            },
+        }
+    }
+
+    fn size(&self) -> Vec2 {
+        let fsize = *self.style.font_size.borrow();
+        let dim = text::measure_text(&self.text, Some(&self.font), fsize as u16, self.scale);
+
+        vec2(dim.width + 2.0 * PADDING, dim.height + 2.0 * PADDING)
+    }
+
+    fn place(&mut self, x: f32, y: f32, size: Vec2) {
+        self.style.x = Value::Absolute(x);
+        self.style.y = Value::Absolute(y);
+        self.style.width = Value::Absolute(size.x);
+        self.style.height = Value::Absolute(size.y);
+    }
+
+    fn draw(&self) {
+        if self.filled {
+            self.style.draw_bg();
+        }
+
+        let color = if self.selected {
+            &self.style.theme.text
+        } else {
+            &self.style.theme.ghost
+        };
+        super::text::print_text(
+            &self.style,
+            &self.text,
+            PrintOptions {
+                font: Some(Rc::clone(&self.font)),
+                font_scale: Some(self.scale),
+                color: Some(*color.borrow()),
+                ..PrintOptions::default()
+            },
+        );
+    }
+}
+
+pub struct Label {
+    pub text: String,
+    color: Rc<RefCell<Color>>,
+    style: Style,
+    font: Rc<Font>,
+}
+
+impl Label {
+    pub fn new(text: &str, color: &Rc<RefCell<Color>>, style: &Style, font: Rc<Font>) -> Self {
+        Label {
+            text: text.to_string(),
+            color: Rc::clone(color),
+            font,
+            style: Style {
+                font_size: Rc::clone(&style.font_size),
+                ..Style::default()
+            },
+        }
+    }
+
+    fn size(&self) -> Vec2 {
+        let fsize = *self.style.font_size.borrow();
+        let width = text::measure_text(&self.text, Some(&self.font), fsize as u16, 1.0).width;
+
+        vec2(width, fsize)
+    }
+
+    fn place(&mut self, x: f32, y: f32) {
+        self.style.x = Value::Absolute(x);
+        self.style.y = Value::Absolute(y);
+    }
+
+    fn draw(&self) {
+        super::text::print_text(
+            &self.style,
+            &self.text,
+            PrintOptions {
+                font: Some(Rc::clone(&self.font)),
+                color: Some(*self.color.borrow()),
+                ..PrintOptions::default()
+            },
+        );
+    }
+}
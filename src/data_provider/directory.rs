@@ -0,0 +1,127 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rand::RngCore;
+
+use super::text::{paragraphs, source_name};
+use super::{
+    parse, parse_quotes, parse_words, Data, DataProvider, Format, Pack, Quote, Sampling,
+    BUNDLED_QUOTES, BUNDLED_WORDS,
+};
+use crate::app::Mode;
+
+pub struct DirectoryProvider {
+    data: Data,
+}
+
+impl DirectoryProvider {
+    /// Reads each `.json` and `.csv` file in `dir` as a words or a quotes file, and each
+    /// `.txt` file as a words file with one word per line, or else as quotes, one per
+    /// paragraph, with the file name as source. Whatever the folder has none of comes
+    /// from the bundled data.
+    pub fn new(dir: &Path, sampling: &Sampling) -> Result<Self, Box<dyn Error>> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.is_file())
+            .collect();
+        paths.sort();
+
+        let mut words = vec![];
+        let mut weights = Some(vec![]);
+        let mut quotes: Vec<Quote> = vec![];
+
+        for path in paths {
+            let pack = match path.extension().and_then(|e| e.to_str()) {
+                Some("json" | "csv") => {
+                    let s = fs::read_to_string(&path)?;
+                    parse(&s, Format::detect(&path, &s))
+                        .map_err(|e| format!("{}: {}", path.display(), e))?
+                }
+                Some("txt") => {
+                    let s = fs::read_to_string(&path)?;
+                    parse(&s, Format::Text)
+                        .unwrap_or_else(|_| Pack::Quotes(paragraphs(&s, &source_name(&path))))
+                }
+                _ => continue,
+            };
+
+            match pack {
+                Pack::Words((w, wt)) => {
+                    match (&mut weights, wt) {
+                        (Some(all), Some(wt)) => all.extend(wt),
+                        _ => weights = None,
+                    }
+                    words.extend(w);
+                }
+                Pack::Quotes(qs) => quotes.extend(
+                    qs.into_iter()
+                        .filter(|q| !q.quote.is_empty() && q.quote != q.source),
+                ),
+            }
+        }
+
+        if words.is_empty() {
+            (words, weights) = parse_words(BUNDLED_WORDS, Format::Json)?;
+        }
+        if quotes.is_empty() {
+            quotes = parse_quotes(BUNDLED_QUOTES, Format::Json)?;
+        }
+
+        Ok(DirectoryProvider {
+            data: Data::new(words, weights, quotes)?.with_sampling(sampling)?,
+        })
+    }
+}
+
+impl DataProvider for DirectoryProvider {
+    fn next_text(&self, mode: &Mode, rng: &mut dyn RngCore) -> Mode {
+        self.data.next_text(mode, rng)
+    }
+
+    fn quotes(&self) -> &[Quote] {
+        self.data.quotes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_every_file() {
+        let dir = std::env::temp_dir().join(format!("typing_test_dir_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(dir.join("a.json"), r#"["alpha", "beta"]"#).unwrap();
+        fs::write(dir.join("b.json"), r#"["gamma"]"#).unwrap();
+        fs::write(dir.join("c.json"), r#"{"Someone": ["Hello there."]}"#).unwrap();
+        fs::write(dir.join("d.txt"), "delta\nepsilon\n").unwrap();
+        fs::write(dir.join("e.csv"), "quote,source\n\"Bye, then.\",Other\n").unwrap();
+        fs::write(dir.join("story.txt"), "Once upon\na time.\n\nThe end.").unwrap();
+        fs::write(dir.join("ignored.md"), "# nothing").unwrap();
+
+        let provider = DirectoryProvider::new(&dir, &Sampling::default()).unwrap();
+
+        assert_eq!(
+            provider.data.get_words(),
+            &vec!["alpha", "beta", "gamma", "delta", "epsilon"]
+        );
+        assert_eq!(
+            provider
+                .quotes()
+                .iter()
+                .map(|q| (&q.source[..], &q.quote[..]))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Someone", "Hello there."),
+                ("Other", "Bye, then."),
+                ("story", "Once upon a time."),
+                ("story", "The end.")
+            ]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
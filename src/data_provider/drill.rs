@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{parse_words, Format};
+
+const BUNDLED_NGRAMS: &str = include_str!("../data/ngrams.json");
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum NgramSize {
+    #[default]
+    Bigrams,
+    Trigrams,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Drill {
+    #[serde(default)]
+    pub ngrams: NgramSize,
+
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+
+    #[serde(default = "default_repetitions")]
+    pub repetitions: usize,
+
+    #[serde(default = "default_target_wpm")]
+    pub target_wpm: f32,
+
+    #[serde(default = "default_target_accuracy")]
+    pub target_accuracy: i32,
+
+    #[serde(default)]
+    pub position: usize,
+}
+
+fn default_repetitions() -> usize {
+    10
+}
+
+fn default_target_wpm() -> f32 {
+    40.0
+}
+
+fn default_target_accuracy() -> i32 {
+    95
+}
+
+impl Default for Drill {
+    fn default() -> Self {
+        Drill {
+            ngrams: NgramSize::default(),
+            file: None,
+            repetitions: default_repetitions(),
+            target_wpm: default_target_wpm(),
+            target_accuracy: default_target_accuracy(),
+            position: 0,
+        }
+    }
+}
+
+impl Drill {
+    pub fn load(&self) -> Result<Vec<String>, String> {
+        if let Some(path) = &self.file {
+            let ngrams = load_file(path)?;
+            if ngrams.is_empty() {
+                return Err(format!("{} has no n-grams", path.display()));
+            }
+            return Ok(ngrams);
+        }
+
+        let mut bundled: BTreeMap<String, Vec<String>> =
+            serde_json::from_str(BUNDLED_NGRAMS).map_err(|e| e.to_string())?;
+
+        let key = match self.ngrams {
+            NgramSize::Bigrams => "bigrams",
+            NgramSize::Trigrams => "trigrams",
+        };
+        Ok(bundled.remove(key).unwrap_or_default())
+    }
+
+    pub fn current(&self, ngrams: &[String]) -> String {
+        if ngrams.is_empty() {
+            return String::new();
+        }
+        ngrams[self.position % ngrams.len()].clone()
+    }
+
+    pub fn passed(&self, wpm: f32, accuracy: i32) -> bool {
+        wpm >= self.target_wpm && accuracy >= self.target_accuracy
+    }
+}
+
+fn load_file(path: &Path) -> Result<Vec<String>, String> {
+    let s = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    parse_words(&s, Format::detect(path, &s))
+        .map(|(ngrams, _)| ngrams)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn drill_line(ngram: &str, repetitions: usize) -> String {
+    vec![ngram; repetitions.max(1)].join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled() {
+        let bigrams = Drill::default().load().unwrap();
+        let trigrams = Drill {
+            ngrams: NgramSize::Trigrams,
+            ..Drill::default()
+        }
+        .load()
+        .unwrap();
+
+        assert_eq!(bigrams.len(), 50);
+        assert!(bigrams.iter().all(|n| n.len() == 2));
+        assert_eq!(trigrams.len(), 50);
+        assert!(trigrams.iter().all(|n| n.len() == 3));
+
+        assert!(Drill {
+            file: Some(PathBuf::from("no such file")),
+            ..Drill::default()
+        }
+        .load()
+        .is_err());
+    }
+
+    #[test]
+    fn advancing() {
+        let ngrams = vec!["th".to_string(), "he".to_string()];
+        let mut drill = Drill::default();
+
+        assert_eq!(drill.current(&ngrams), "th");
+        drill.position += 1;
+        assert_eq!(drill.current(&ngrams), "he");
+        drill.position += 1;
+        assert_eq!(drill.current(&ngrams), "th");
+
+        assert_eq!(drill_line("th", 3), "th th th");
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::{Quote, Words};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A json array of words, most common first, or of `[word, frequency]` pairs for
+    /// words. A json object with the sources as keys and an array of quotes as values
+    /// for quotes.
+    Json,
+    MonkeytypeWords,
+    MonkeytypeQuotes,
+    Text,
+    /// `word` or `word,frequency` rows for words, `quote,source` rows for quotes. A
+    /// header row naming the columns is optional.
+    Csv,
+}
+
+impl Format {
+    pub fn detect(path: &Path, s: &str) -> Format {
+        let path = path.to_string_lossy();
+        // the query and fragment of a url come after the extension
+        let path = if path.contains("://") {
+            path.split(['?', '#']).next().unwrap_or_default()
+        } else {
+            &path
+        };
+
+        match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some("csv") => return Format::Csv,
+            Some("txt") => return Format::Text,
+            _ => (),
+        }
+
+        match s.trim_start().chars().next() {
+            Some('[') => Format::Json,
+            Some('{') => match serde_json::from_str::<serde_json::Value>(s) {
+                Ok(v) if v.get("quotes").is_some_and(|q| q.is_array()) => Format::MonkeytypeQuotes,
+                Ok(v) if v.get("words").is_some_and(|w| w.is_array()) => Format::MonkeytypeWords,
+                _ => Format::Json,
+            },
+            _ => Format::Text,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Pack {
+    Words(Words),
+    Quotes(Vec<Quote>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    /// 0 when the error is about the whole line.
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column: 0,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.column > 0 {
+            write!(
+                f,
+                "line {}, column {}: {}",
+                self.line, self.column, self.message
+            )
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<serde_json::Error> for ParseError {
+    fn from(e: serde_json::Error) -> Self {
+        let message = e.to_string();
+        let suffix = format!(" at line {} column {}", e.line(), e.column());
+
+        ParseError {
+            line: e.line(),
+            column: e.column(),
+            message: message
+                .strip_suffix(&suffix)
+                .unwrap_or(&message)
+                .to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct MonkeytypeLanguage {
+    words: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct MonkeytypeQuotes {
+    quotes: Vec<MonkeytypeQuote>,
+}
+
+#[derive(Deserialize)]
+struct MonkeytypeQuote {
+    text: String,
+    source: String,
+}
+
+pub fn parse(s: &str, format: Format) -> Result<Pack, ParseError> {
+    match format {
+        Format::Json => parse_json(s),
+        Format::MonkeytypeWords => Ok(Pack::Words((
+            serde_json::from_str::<MonkeytypeLanguage>(s)?.words,
+            None,
+        ))),
+        Format::MonkeytypeQuotes => Ok(Pack::Quotes(
+            serde_json::from_str::<MonkeytypeQuotes>(s)?
+                .quotes
+                .into_iter()
+                .map(|q| Quote {
+                    source: q.source,
+                    quote: q.text,
+                })
+                .collect(),
+        )),
+        Format::Text => parse_text(s),
+        Format::Csv => parse_csv(s),
+    }
+}
+
+pub fn parse_words(s: &str, format: Format) -> Result<Words, ParseError> {
+    match parse(s, format)? {
+        Pack::Words(words) => Ok(words),
+        Pack::Quotes(_) => Err(ParseError::new(
+            1,
+            "this is a quotes file, not a words file",
+        )),
+    }
+}
+
+pub fn parse_raw_quotes(s: &str, format: Format) -> Result<Vec<Quote>, ParseError> {
+    match parse(s, format)? {
+        Pack::Quotes(quotes) => Ok(quotes),
+        Pack::Words(_) => Err(ParseError::new(
+            1,
+            "this is a words file, not a quotes file",
+        )),
+    }
+}
+
+fn parse_json(s: &str) -> Result<Pack, ParseError> {
+    let mut chars = s.trim_start().chars();
+
+    match chars.next() {
+        Some('{') => Ok(Pack::Quotes(
+            serde_json::from_str::<BTreeMap<String, Vec<String>>>(s)?
+                .into_iter()
+                .flat_map(|(source, qs)| {
+                    qs.into_iter().map(move |quote| Quote {
+                        source: source.clone(),
+                        quote,
+                    })
+                })
+                .collect(),
+        )),
+        // an array of arrays is a list of [word, frequency] pairs
+        Some('[') if chars.find(|c| !c.is_whitespace()) == Some('[') => {
+            let (words, weights) = serde_json::from_str::<Vec<(String, f64)>>(s)?
+                .into_iter()
+                .unzip();
+            Ok(Pack::Words((words, Some(weights))))
+        }
+        _ => Ok(Pack::Words((serde_json::from_str::<Vec<String>>(s)?, None))),
+    }
+}
+
+fn parse_text(s: &str) -> Result<Pack, ParseError> {
+    let mut words = vec![];
+
+    for (i, line) in s.lines().enumerate() {
+        let word = line.trim();
+
+        if word.contains(char::is_whitespace) {
+            return Err(ParseError::new(
+                i + 1,
+                format!("{:?} is more than one word, put one word per line", word),
+            ));
+        }
+        if !word.is_empty() {
+            words.push(word.to_string());
+        }
+    }
+
+    Ok(Pack::Words((words, None)))
+}
+
+fn parse_csv(s: &str) -> Result<Pack, ParseError> {
+    let mut records = csv_records(s)?.into_iter().peekable();
+
+    let Some((_, first)) = records.peek() else {
+        return Ok(Pack::Words((vec![], None)));
+    };
+
+    let header: Vec<String> = first.iter().map(|f| f.trim().to_lowercase()).collect();
+    let column = |names: &[&str]| header.iter().position(|h| names.contains(&&h[..]));
+
+    let (quote_column, source_column) = (column(&["quote", "text"]), column(&["source"]));
+    let is_header = header[0] == "word" || quote_column.is_some() && source_column.is_some();
+
+    let is_quotes = match (quote_column, source_column) {
+        (Some(_), Some(_)) => true,
+        _ if is_header => false,
+        _ => first.len() == 2 && first[1].trim().parse::<f64>().is_err(),
+    };
+    let fields = if is_header { header.len() } else { first.len() };
+
+    if is_header {
+        records.next();
+    }
+
+    if is_quotes {
+        let (q, src) = (quote_column.unwrap_or(0), source_column.unwrap_or(1));
+        let mut quotes = vec![];
+
+        for (line, record) in records {
+            check_fields(line, &record, fields)?;
+            // quoted fields can span lines, which can't be typed
+            quotes.push(Quote {
+                source: collapse_whitespace(&record[src]),
+                quote: collapse_whitespace(&record[q]),
+            });
+        }
+
+        return Ok(Pack::Quotes(quotes));
+    }
+
+    let mut words = vec![];
+    let mut weights = vec![];
+
+    for (line, record) in records {
+        check_fields(line, &record, fields)?;
+        words.push(record[0].trim().to_string());
+
+        if fields > 1 {
+            weights.push(record[1].trim().parse::<f64>().map_err(|_| {
+                ParseError::new(line, format!("{:?} is not a frequency", record[1]))
+            })?);
+        }
+    }
+
+    Ok(Pack::Words((words, (fields > 1).then_some(weights))))
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+fn check_fields(line: usize, record: &[String], fields: usize) -> Result<(), ParseError> {
+    if record.len() != fields {
+        return Err(ParseError::new(
+            line,
+            format!("expected {} fields, found {}", fields, record.len()),
+        ));
+    }
+    Ok(())
+}
+
+/// Splits csv into records, with the line each one starts on. Fields may be quoted with
+/// `"` to contain commas, line breaks and `""` for a quote. Empty lines are skipped.
+fn csv_records(s: &str) -> Result<Vec<(usize, Vec<String>)>, ParseError> {
+    let mut records = vec![];
+    let mut chars = s.chars().peekable();
+    let mut line = 1;
+
+    while chars.peek().is_some() {
+        let start = line;
+        let mut record = vec![];
+
+        loop {
+            let mut field = String::new();
+
+            if chars.peek() == Some(&'"') {
+                chars.next();
+
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            field.push(c);
+                        }
+                        None => return Err(ParseError::new(start, "unterminated quoted field")),
+                    }
+                }
+
+                if !matches!(chars.peek(), None | Some(',' | '\n' | '\r')) {
+                    return Err(ParseError::new(
+                        line,
+                        "unexpected text after a quoted field",
+                    ));
+                }
+            } else {
+                while let Some(&c) = chars.peek() {
+                    if c == ',' || c == '\n' {
+                        break;
+                    }
+                    field.push(c);
+                    chars.next();
+                }
+            }
+
+            record.push(field.strip_suffix('\r').unwrap_or(&field).to_string());
+
+            match chars.next() {
+                Some(',') => continue,
+                Some('\r') => {
+                    chars.next_if_eq(&'\n');
+                }
+                _ => (),
+            }
+            line += 1;
+            break;
+        }
+
+        if record.len() > 1 || !record[0].trim().is_empty() {
+            records.push((start, record));
+        }
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(v: &[&str]) -> Vec<String> {
+        v.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn quote(source: &str, quote: &str) -> Quote {
+        Quote {
+            source: source.to_string(),
+            quote: quote.to_string(),
+        }
+    }
+
+    #[test]
+    fn detect() {
+        let detect = |path: &str, s: &str| Format::detect(Path::new(path), s);
+
+        assert_eq!(detect("a.csv", "[]"), Format::Csv);
+        assert_eq!(detect("https://example.com/a.txt", "[]"), Format::Text);
+        assert_eq!(
+            detect("https://example.com/a.csv?raw=true#top", "[]"),
+            Format::Csv
+        );
+        assert_eq!(detect("a", " [\"a\"]"), Format::Json);
+        assert_eq!(detect("a.json", r#"{"a": ["b"]}"#), Format::Json);
+        assert_eq!(
+            detect("a.json", r#"{"name": "english", "words": ["a"]}"#),
+            Format::MonkeytypeWords
+        );
+        assert_eq!(
+            detect("a.json", r#"{"language": "english", "quotes": []}"#),
+            Format::MonkeytypeQuotes
+        );
+        assert_eq!(detect("words", "the\nof\n"), Format::Text);
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            parse_words(r#"[["the", 2.5], ["of", 1]]"#, Format::Json),
+            Ok((words(&["the", "of"]), Some(vec![2.5, 1.0])))
+        );
+        assert_eq!(
+            parse_raw_quotes(r#"{"B": ["b"], "A": ["a", "c"]}"#, Format::Json),
+            Ok(vec![quote("A", "a"), quote("A", "c"), quote("B", "b")])
+        );
+
+        let e = parse_words("[\n  \"the\",\n  3\n]", Format::Json).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 3));
+        assert_eq!(e.message, "invalid type: integer `3`, expected a string");
+        assert!(parse_words(r#"{"A": ["a"]}"#, Format::Json).is_err());
+    }
+
+    #[test]
+    fn monkeytype() {
+        assert_eq!(
+            parse_words(
+                r#"{"name": "english", "noLazyMode": true, "words": ["the", "be"]}"#,
+                Format::MonkeytypeWords
+            ),
+            Ok((words(&["the", "be"]), None))
+        );
+        assert_eq!(
+            parse_raw_quotes(
+                r#"{"language": "english", "groups": [[0, 100]], "quotes": [
+                    {"text": "Hello there.", "source": "Someone", "length": 12, "id": 1}
+                ]}"#,
+                Format::MonkeytypeQuotes
+            ),
+            Ok(vec![quote("Someone", "Hello there.")])
+        );
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            parse_words("the\r\n  of\n\nand\n", Format::Text),
+            Ok((words(&["the", "of", "and"]), None))
+        );
+        assert_eq!(
+            parse_words("the\nof it\n", Format::Text),
+            Err(ParseError::new(
+                2,
+                "\"of it\" is more than one word, put one word per line"
+            ))
+        );
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            parse_words("word,frequency\nthe,3\r\nof, 1.5\n", Format::Csv),
+            Ok((words(&["the", "of"]), Some(vec![3.0, 1.5])))
+        );
+        assert_eq!(
+            parse_words("the\n\nof\n", Format::Csv),
+            Ok((words(&["the", "of"]), None))
+        );
+        assert_eq!(
+            parse_raw_quotes(
+                "source,quote\nSomeone,\"Well, \"\"hello\"\"\nthere.\"\nOther,Bye.\n",
+                Format::Csv
+            ),
+            Ok(vec![
+                quote("Someone", "Well, \"hello\" there."),
+                quote("Other", "Bye.")
+            ])
+        );
+        assert_eq!(
+            parse_raw_quotes("\" Hello\tthere. \",Someone\n", Format::Csv),
+            Ok(vec![quote("Someone", "Hello there.")])
+        );
+
+        assert_eq!(
+            parse_words("the,3\nof,often\n", Format::Csv),
+            Err(ParseError::new(2, "\"often\" is not a frequency"))
+        );
+        assert_eq!(
+            parse_words("the,3\nof\n", Format::Csv),
+            Err(ParseError::new(2, "expected 2 fields, found 1"))
+        );
+        assert_eq!(
+            parse_raw_quotes("a,b\n\"c,d\n", Format::Csv),
+            Err(ParseError::new(2, "unterminated quoted field"))
+        );
+    }
+}
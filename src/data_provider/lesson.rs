@@ -0,0 +1,213 @@
+use std::sync::OnceLock;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use super::{parse_words, Format, BUNDLED_WORDS};
+
+pub const KEY_ORDER: &str = "fjdkslaghrueiotnwmcypvbxqz";
+
+pub const FIRST_KEYS: usize = 7;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Lessons {
+    #[serde(default = "default_unlocked")]
+    pub unlocked: usize,
+
+    #[serde(default = "default_target_wpm")]
+    pub target_wpm: f32,
+
+    #[serde(default = "default_target_accuracy")]
+    pub target_accuracy: i32,
+}
+
+fn default_unlocked() -> usize {
+    FIRST_KEYS
+}
+
+fn default_target_wpm() -> f32 {
+    25.0
+}
+
+fn default_target_accuracy() -> i32 {
+    95
+}
+
+impl Default for Lessons {
+    fn default() -> Self {
+        Lessons {
+            unlocked: default_unlocked(),
+            target_wpm: default_target_wpm(),
+            target_accuracy: default_target_accuracy(),
+        }
+    }
+}
+
+impl Lessons {
+    pub fn keys(&self) -> &str {
+        &KEY_ORDER[..self.unlocked.clamp(FIRST_KEYS, KEY_ORDER.len())]
+    }
+
+    pub fn next_key(&self) -> Option<char> {
+        KEY_ORDER.chars().nth(self.unlocked)
+    }
+
+    pub fn passed(&self, wpm: f32, accuracy: i32) -> bool {
+        wpm >= self.target_wpm && accuracy >= self.target_accuracy
+    }
+}
+
+const END: usize = 26;
+const MIN_LENGTH: usize = 2;
+const MAX_LENGTH: usize = 7;
+
+/// How often each letter follows another in the bundled words, `END` standing for the
+/// start and the end of a word.
+struct Chain {
+    counts: [[u32; 27]; 27],
+}
+
+impl Chain {
+    fn get() -> &'static Chain {
+        static CHAIN: OnceLock<Chain> = OnceLock::new();
+
+        CHAIN.get_or_init(|| {
+            let (words, _) = parse_words(BUNDLED_WORDS, Format::Json).unwrap_or_default();
+            Chain::new(&words)
+        })
+    }
+
+    fn new(words: &[String]) -> Self {
+        let mut counts = [[0; 27]; 27];
+
+        for word in words {
+            let word = word.to_lowercase();
+
+            if !word.chars().all(|c| c.is_ascii_lowercase()) {
+                continue;
+            }
+
+            let mut prev = END;
+            for i in word.bytes().map(|b| (b - b'a') as usize) {
+                counts[prev][i] += 1;
+                prev = i;
+            }
+            counts[prev][END] += 1;
+        }
+
+        Chain { counts }
+    }
+
+    fn word(&self, allowed: &[bool; 27], rng: &mut dyn RngCore) -> String {
+        let mut word = String::new();
+        let mut prev = END;
+
+        loop {
+            let weights: Vec<u32> = (0..27)
+                .map(|i| match i {
+                    END if word.len() < MIN_LENGTH => 0,
+                    END => self.counts[prev][END],
+                    _ if !allowed[i] || word.len() >= MAX_LENGTH => 0,
+                    _ => self.counts[prev][i],
+                })
+                .collect();
+
+            // pairs English never has still need a way out
+            let next = match WeightedIndex::new(&weights) {
+                Ok(dist) => dist.sample(rng),
+                Err(_) if word.len() >= MIN_LENGTH => END,
+                Err(_) => {
+                    let letters: Vec<usize> = (0..END).filter(|&i| allowed[i]).collect();
+                    letters[rng.gen_range(0..letters.len())]
+                }
+            };
+
+            if next == END {
+                return word;
+            }
+
+            word.push((b'a' + next as u8) as char);
+            prev = next;
+        }
+    }
+}
+
+/// `n` pseudo-words made of only `keys`, following the letter pairs of the bundled
+/// words so they're pronounceable. Every other word has the newest key, the last of
+/// `keys`, in it when possible.
+pub fn pseudo_words(keys: &str, n: usize, rng: &mut dyn RngCore) -> String {
+    let mut allowed = [false; 27];
+    for c in keys.chars().filter(|c| c.is_ascii_lowercase()) {
+        allowed[(c as u8 - b'a') as usize] = true;
+    }
+
+    if !allowed.iter().any(|a| *a) {
+        return String::new();
+    }
+
+    let chain = Chain::get();
+    let newest = keys.chars().last();
+
+    (0..n)
+        .map(|i| {
+            let mut word = chain.word(&allowed, rng);
+
+            for _ in 0..20 {
+                if i % 2 == 1 || newest.is_none_or(|c| word.contains(c)) {
+                    break;
+                }
+                word = chain.word(&allowed, rng);
+            }
+
+            word
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn key_order() {
+        let mut keys: Vec<char> = KEY_ORDER.chars().collect();
+        keys.sort();
+        keys.dedup();
+
+        assert_eq!(keys.len(), 26);
+        assert_eq!(Lessons::default().keys(), "fjdksla");
+        assert_eq!(Lessons::default().next_key(), Some('g'));
+    }
+
+    #[test]
+    fn only_unlocked_keys() {
+        let text = pseudo_words("fjdksla", 200, &mut StdRng::seed_from_u64(1));
+        let words: Vec<&str> = text.split(' ').collect();
+
+        assert_eq!(words.len(), 200);
+        assert!(words
+            .iter()
+            .all(|w| (MIN_LENGTH..=MAX_LENGTH).contains(&w.len())));
+        assert!(text.chars().all(|c| "fjdksla ".contains(c)));
+        assert!(words.iter().step_by(2).filter(|w| w.contains('a')).count() > 90);
+
+        assert_eq!(
+            text,
+            pseudo_words("fjdksla", 200, &mut StdRng::seed_from_u64(1))
+        );
+    }
+
+    #[test]
+    fn passing() {
+        let lessons = Lessons::default();
+
+        assert!(lessons.passed(25.0, 95));
+        assert!(!lessons.passed(40.0, 90));
+        assert!(!lessons.passed(20.0, 100));
+    }
+}
@@ -0,0 +1,287 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tokio::runtime::Runtime;
+
+use super::{
+    parse_quotes, parse_words, validate_quotes, validate_words, Data, DataProvider, Format, Quote,
+    Sampling, BUNDLED_QUOTES, BUNDLED_WORDS,
+};
+use crate::app::Mode;
+
+/// How long connecting to a server may take at most, whatever the `timeout`.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Online {
+    #[serde(default)]
+    pub words_url: Option<String>,
+
+    #[serde(default)]
+    pub quotes_url: Option<String>,
+
+    #[serde(default)]
+    pub cache_dir: Option<PathBuf>,
+
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+}
+
+fn default_timeout() -> u64 {
+    10
+}
+
+impl Default for Online {
+    fn default() -> Self {
+        Online {
+            words_url: None,
+            quotes_url: None,
+            cache_dir: None,
+            timeout: default_timeout(),
+        }
+    }
+}
+
+impl Online {
+    pub fn get_cache_dir(&self) -> PathBuf {
+        match &self.cache_dir {
+            Some(dir) => dir.clone(),
+            None => dirs::cache_dir().unwrap_or_default().join("typing_test"),
+        }
+    }
+}
+
+pub struct HttpProvider {
+    data: Data,
+}
+
+impl HttpProvider {
+    pub fn new(online: &Online, sampling: &Sampling) -> Result<Self, Box<dyn Error>> {
+        let rt = Runtime::new()?;
+        let cache_dir = online.get_cache_dir();
+        // a server that doesn't answer can't keep the window from opening
+        let timeout = Duration::from_secs(online.timeout);
+        let client = reqwest::Client::builder()
+            .connect_timeout(timeout.min(CONNECT_TIMEOUT))
+            .timeout(timeout)
+            .build()?;
+
+        let (words, weights) = match &online.words_url {
+            Some(url) => fetch_pack(&rt, &client, url, &cache_dir.join("words.json"), |s| {
+                let words = parse_words(s, Format::detect(Path::new(url), s))?;
+                validate_words(&words.0)?;
+                Ok(words)
+            })
+            .or_else(|e| {
+                println!("Can't get words, using the bundled ones. {}", e);
+                parse_words(BUNDLED_WORDS, Format::Json)
+            })?,
+            None => parse_words(BUNDLED_WORDS, Format::Json)?,
+        };
+
+        let quotes = match &online.quotes_url {
+            Some(url) => fetch_pack(&rt, &client, url, &cache_dir.join("quotes.json"), |s| {
+                let quotes = parse_quotes(s, Format::detect(Path::new(url), s))?;
+                validate_quotes(&quotes)?;
+                Ok(quotes)
+            })
+            .or_else(|e| {
+                println!("Can't get quotes, using the bundled ones. {}", e);
+                parse_quotes(BUNDLED_QUOTES, Format::Json)
+            })?,
+            None => parse_quotes(BUNDLED_QUOTES, Format::Json)?,
+        };
+
+        Ok(HttpProvider {
+            data: Data::new(words, weights, quotes)?.with_sampling(sampling)?,
+        })
+    }
+}
+
+impl DataProvider for HttpProvider {
+    fn next_text(&self, mode: &Mode, rng: &mut dyn RngCore) -> Mode {
+        self.data.next_text(mode, rng)
+    }
+
+    fn quotes(&self) -> &[Quote] {
+        self.data.quotes()
+    }
+}
+
+/// Fetches the pack at `url` and keeps a copy in `cache` if `parse` accepts it. When the
+/// fetch fails or the pack is broken the cached copy is parsed instead.
+fn fetch_pack<T>(
+    rt: &Runtime,
+    client: &reqwest::Client,
+    url: &str,
+    cache: &Path,
+    parse: impl Fn(&str) -> Result<T, Box<dyn Error>>,
+) -> Result<T, Box<dyn Error>> {
+    let fetched = rt.block_on(fetch(client, url)).and_then(|s| {
+        let pack = parse(&s)?;
+        Ok((s, pack))
+    });
+
+    match fetched {
+        Ok((s, pack)) => {
+            if let Err(e) = cache
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(cache, s))
+            {
+                println!("Can't cache {}. {}", url, e);
+            }
+            Ok(pack)
+        }
+        Err(e) => {
+            println!("Can't get {}, using the cache. {}", url, e);
+            parse(&fs::read_to_string(cache)?)
+        }
+    }
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> Result<String, Box<dyn Error>> {
+    Ok(client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn serve(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf);
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        format!("http://{}", addr)
+    }
+
+    fn hanging() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            let _streams: Vec<_> = listener.incoming().collect();
+        });
+
+        format!("http://{}", addr)
+    }
+
+    fn unreachable() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("typing_test_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn fetches_and_caches() {
+        let dir = cache_dir("fetch");
+        let online = Online {
+            words_url: Some(serve(r#"["alpha", "beta", "gamma"]"#)),
+            quotes_url: Some(serve(r#"{"Someone": ["Hello there friend."]}"#)),
+            cache_dir: Some(dir.clone()),
+            ..Online::default()
+        };
+
+        let provider = HttpProvider::new(&online, &Sampling::default()).unwrap();
+
+        assert_eq!(provider.data.get_words(), &vec!["alpha", "beta", "gamma"]);
+        assert_eq!(provider.quotes()[0].quote, "Hello there friend.");
+        assert!(dir.join("words.json").exists());
+        assert!(dir.join("quotes.json").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn falls_back_to_cache() {
+        let dir = cache_dir("cache");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("words.json"), r#"["cached", "words"]"#).unwrap();
+
+        // the server is up but the pack doesn't pass validation
+        let online = Online {
+            words_url: Some(serve(r#"["two  spaces", ""]"#)),
+            quotes_url: None,
+            cache_dir: Some(dir.clone()),
+            ..Online::default()
+        };
+        let provider = HttpProvider::new(&online, &Sampling::default()).unwrap();
+        assert_eq!(provider.data.get_words(), &vec!["cached", "words"]);
+
+        let online = Online {
+            words_url: Some(unreachable()),
+            ..online
+        };
+        let provider = HttpProvider::new(&online, &Sampling::default()).unwrap();
+        assert_eq!(provider.data.get_words(), &vec!["cached", "words"]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn times_out() {
+        let dir = cache_dir("timeout");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("words.json"), r#"["cached", "words"]"#).unwrap();
+
+        let online = Online {
+            words_url: Some(hanging()),
+            cache_dir: Some(dir.clone()),
+            timeout: 1,
+            ..Online::default()
+        };
+        let provider = HttpProvider::new(&online, &Sampling::default()).unwrap();
+        assert_eq!(provider.data.get_words(), &vec!["cached", "words"]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn falls_back_to_bundled() {
+        let online = Online {
+            words_url: Some(unreachable()),
+            quotes_url: Some(unreachable()),
+            cache_dir: Some(cache_dir("bundled")),
+            ..Online::default()
+        };
+
+        let provider = HttpProvider::new(&online, &Sampling::default()).unwrap();
+        let bundled = Data::new_offline(None, None).unwrap();
+
+        assert_eq!(provider.data.get_words(), bundled.get_words());
+        assert_eq!(provider.quotes(), bundled.quotes());
+    }
+}
@@ -0,0 +1,57 @@
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Practice {
+    #[serde(default = "default_repeat")]
+    pub repeat: usize,
+
+    #[serde(default)]
+    pub slowest: usize,
+}
+
+fn default_repeat() -> usize {
+    3
+}
+
+impl Default for Practice {
+    fn default() -> Self {
+        Practice {
+            repeat: default_repeat(),
+            slowest: 0,
+        }
+    }
+}
+
+pub fn practice_text(words: &[String], repeat: usize, rng: &mut dyn RngCore) -> String {
+    let mut text: Vec<&str> = words
+        .iter()
+        .flat_map(|w| vec![&w[..]; repeat.max(1)])
+        .collect();
+    text.shuffle(rng);
+
+    text.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn repeats_every_word() {
+        let words = vec!["their".to_string(), "receive".to_string()];
+        let text = practice_text(&words, 3, &mut StdRng::seed_from_u64(1));
+
+        let mut typed: Vec<&str> = text.split(' ').collect();
+        typed.sort();
+
+        assert_eq!(
+            typed,
+            vec!["receive", "receive", "receive", "their", "their", "their"]
+        );
+    }
+}
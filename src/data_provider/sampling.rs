@@ -0,0 +1,307 @@
+use std::collections::VecDeque;
+
+use rand::distributions::{Distribution as _, WeightedIndex};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Distribution {
+    #[default]
+    Uniform,
+    Frequency,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Sampling {
+    #[serde(default)]
+    pub distribution: Distribution,
+
+    /// Zipf exponent used when the list has no weights, higher favours common words more.
+    #[serde(default = "default_exponent")]
+    pub exponent: f64,
+
+    #[serde(default)]
+    pub top_n: Option<usize>,
+
+    /// A word can't come back until this many other words were drawn.
+    #[serde(default = "default_no_repeat")]
+    pub no_repeat: usize,
+}
+
+fn default_exponent() -> f64 {
+    1.0
+}
+
+fn default_no_repeat() -> usize {
+    1
+}
+
+impl Default for Sampling {
+    fn default() -> Self {
+        Sampling {
+            distribution: Distribution::default(),
+            exponent: default_exponent(),
+            top_n: None,
+            no_repeat: default_no_repeat(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Sampler {
+    pool: Vec<usize>,
+    weights: Option<Vec<f64>>,
+    no_repeat: usize,
+}
+
+impl Sampler {
+    pub fn new(sampling: &Sampling, len: usize, weights: Option<&[f64]>) -> Result<Self, String> {
+        let mut pool: Vec<usize> = match weights {
+            Some(w) => (0..len).filter(|&i| w[i] > 0.0).collect(),
+            None => (0..len).collect(),
+        };
+
+        if pool.is_empty() {
+            return Err(match weights {
+                Some(_) if len > 0 => "every word has a weight of 0".to_string(),
+                _ => "there are no words".to_string(),
+            });
+        }
+
+        if let Some(w) = weights {
+            if let Some(&i) = pool.iter().find(|&&i| !w[i].is_finite()) {
+                return Err(format!("word {} has a weight of {}", i + 1, w[i]));
+            }
+            pool.sort_by(|&a, &b| w[b].total_cmp(&w[a]));
+        }
+
+        if let Some(n) = sampling.top_n {
+            pool.truncate(n.max(1));
+        }
+
+        let weights = match sampling.distribution {
+            Distribution::Uniform => None,
+            Distribution::Frequency => {
+                let weights: Vec<f64> = pool
+                    .iter()
+                    .enumerate()
+                    .map(|(rank, &i)| match weights {
+                        Some(w) => w[i],
+                        None => 1.0 / ((rank + 1) as f64).powf(sampling.exponent),
+                    })
+                    .collect();
+                WeightedIndex::new(&weights)
+                    .map_err(|e| format!("can't weight the words: {}", e))?;
+                Some(weights)
+            }
+        };
+
+        Ok(Sampler {
+            no_repeat: sampling.no_repeat.min(pool.len().saturating_sub(1)),
+            pool,
+            weights,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pool.is_empty()
+    }
+
+    pub fn draw(&self, rng: &mut impl Rng) -> usize {
+        self.pool[self.draw_except(&[], rng)]
+    }
+
+    pub fn draw_n(&self, n: usize, rng: &mut impl Rng) -> Vec<usize> {
+        let mut v = Vec::with_capacity(n);
+        let mut recent = VecDeque::with_capacity(self.no_repeat + 1);
+        let mut left_out = vec![false; self.pool.len()];
+
+        for _ in 0..n {
+            let at = self.draw_except(&left_out, rng);
+            v.push(self.pool[at]);
+
+            left_out[at] = true;
+            recent.push_back(at);
+            if recent.len() > self.no_repeat {
+                if let Some(at) = recent.pop_front() {
+                    left_out[at] = false;
+                }
+            }
+        }
+
+        v
+    }
+
+    /// A position in `pool` that isn't `left_out`, of which there is always one since the
+    /// no-repeat window is smaller than the pool.
+    fn draw_except(&self, left_out: &[bool], rng: &mut impl Rng) -> usize {
+        let is_left_out = |at: usize| left_out.get(at).copied().unwrap_or(false);
+
+        if let Some(weights) = &self.weights {
+            let weights = weights
+                .iter()
+                .enumerate()
+                .map(|(at, &w)| if is_left_out(at) { 0.0 } else { w });
+            // the words left can all be too rare to have a weight above 0, then they're
+            // drawn as if they were all as common
+            if let Ok(index) = WeightedIndex::new(weights) {
+                return index.sample(rng);
+            }
+        }
+
+        let left: Vec<usize> = (0..self.pool.len())
+            .filter(|&at| !is_left_out(at))
+            .collect();
+        left[rng.gen_range(0..left.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn frequency_favours_common_words() {
+        let sampler = Sampler::new(
+            &Sampling {
+                distribution: Distribution::Frequency,
+                ..Sampling::default()
+            },
+            1000,
+            None,
+        )
+        .unwrap();
+
+        let drawn = sampler.draw_n(10000, &mut StdRng::seed_from_u64(1));
+        let top = drawn.iter().filter(|&&i| i < 10).count();
+        let bottom = drawn.iter().filter(|&&i| i >= 990).count();
+
+        assert!(top > 10 * bottom, "{} vs {}", top, bottom);
+    }
+
+    #[test]
+    fn weights_and_top_n() {
+        let weights = [1.0, 0.0, 50.0, 20.0, 5.0];
+        let sampler = Sampler::new(
+            &Sampling {
+                distribution: Distribution::Frequency,
+                top_n: Some(2),
+                no_repeat: 0,
+                ..Sampling::default()
+            },
+            weights.len(),
+            Some(&weights),
+        )
+        .unwrap();
+
+        let drawn = sampler.draw_n(1000, &mut StdRng::seed_from_u64(2));
+
+        assert!(drawn.iter().all(|&i| i == 2 || i == 3));
+        assert!(drawn.contains(&2) && drawn.contains(&3));
+    }
+
+    #[test]
+    fn no_repeat_window() {
+        let sampler = Sampler::new(
+            &Sampling {
+                no_repeat: 4,
+                ..Sampling::default()
+            },
+            6,
+            None,
+        )
+        .unwrap();
+
+        let drawn = sampler.draw_n(500, &mut StdRng::seed_from_u64(3));
+
+        for window in drawn.windows(5) {
+            for (i, a) in window.iter().enumerate() {
+                assert!(!window[i + 1..].contains(a), "{:?}", window);
+            }
+        }
+    }
+
+    #[test]
+    fn nothing_to_draw() {
+        let sampling = Sampling::default();
+
+        assert!(Sampler::new(&sampling, 0, None).is_err());
+        assert!(Sampler::new(&sampling, 2, Some(&[0.0, 0.0])).is_err());
+        assert!(Sampler::new(
+            &Sampling {
+                distribution: Distribution::Frequency,
+                ..sampling
+            },
+            2,
+            Some(&[1.0, f64::INFINITY]),
+        )
+        .is_err());
+        assert!(Sampler::new(
+            &Sampling {
+                distribution: Distribution::Frequency,
+                exponent: f64::NAN,
+                ..Sampling::default()
+            },
+            2,
+            None,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn skewed_weights_and_window_of_the_whole_list() {
+        let weights = [1e6, 1.0, 1e-3, 1e-9, 1e-300];
+        let sampler = Sampler::new(
+            &Sampling {
+                distribution: Distribution::Frequency,
+                no_repeat: weights.len() - 1,
+                ..Sampling::default()
+            },
+            weights.len(),
+            Some(&weights),
+        )
+        .unwrap();
+
+        let drawn = sampler.draw_n(100, &mut StdRng::seed_from_u64(5));
+
+        // every word comes up once before any comes back, the rare ones included
+        for window in drawn.chunks(weights.len()) {
+            let mut window = window.to_vec();
+            window.sort();
+            assert_eq!(window, vec![0, 1, 2, 3, 4]);
+        }
+
+        // and the same with weights too small to add up to anything but 0
+        let sampler = Sampler::new(
+            &Sampling {
+                distribution: Distribution::Frequency,
+                exponent: 2000.0,
+                no_repeat: 2,
+                ..Sampling::default()
+            },
+            3,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(sampler.draw_n(30, &mut StdRng::seed_from_u64(6)).len(), 30);
+    }
+
+    #[test]
+    fn window_larger_than_list() {
+        let sampler = Sampler::new(
+            &Sampling {
+                no_repeat: 10,
+                ..Sampling::default()
+            },
+            3,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(sampler.draw_n(30, &mut StdRng::seed_from_u64(4)).len(), 30);
+    }
+}
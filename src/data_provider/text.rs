@@ -0,0 +1,111 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use rand::prelude::*;
+
+use super::{DataProvider, Quote};
+use crate::app::Mode;
+
+pub struct TextProvider {
+    words: Vec<String>,
+    paragraphs: Vec<Quote>,
+}
+
+impl TextProvider {
+    pub fn new(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Self::from_text(&fs::read_to_string(path)?, &source_name(path))
+    }
+
+    pub fn from_text(text: &str, source: &str) -> Result<Self, Box<dyn Error>> {
+        let words: Vec<String> = text.split_whitespace().map(|w| w.to_string()).collect();
+
+        if words.is_empty() {
+            return Err(format!("{} has no text", source).into());
+        }
+
+        Ok(TextProvider {
+            words,
+            paragraphs: paragraphs(text, source),
+        })
+    }
+}
+
+impl DataProvider for TextProvider {
+    fn next_text(&self, mode: &Mode, rng: &mut dyn RngCore) -> Mode {
+        match mode.word_count() {
+            Some(n) => {
+                let start = rng.gen_range(0..self.words.len());
+
+                mode.with_text(
+                    (0..n)
+                        .map(|i| &self.words[(start + i) % self.words.len()][..])
+                        .collect::<Vec<&str>>()
+                        .join(" "),
+                )
+            }
+            None => Mode::Quote(self.paragraphs.choose(rng).unwrap().clone()),
+        }
+    }
+
+    fn quotes(&self) -> &[Quote] {
+        &self.paragraphs
+    }
+}
+
+pub(super) fn paragraphs(text: &str, source: &str) -> Vec<Quote> {
+    text.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(|p| p.split_whitespace().collect::<Vec<&str>>().join(" "))
+        .filter(|p| !p.is_empty())
+        .map(|quote| Quote {
+            source: source.to_string(),
+            quote,
+        })
+        .collect()
+}
+
+pub(super) fn source_name(path: &Path) -> String {
+    path.file_stem().map_or_else(
+        || path.display().to_string(),
+        |s| s.to_string_lossy().to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_and_paragraphs() {
+        let provider =
+            TextProvider::from_text("one two\nthree\n\n\n  four   five \n", "notes").unwrap();
+
+        assert_eq!(
+            provider.quotes(),
+            &[
+                Quote {
+                    source: "notes".to_string(),
+                    quote: "one two three".to_string()
+                },
+                Quote {
+                    source: "notes".to_string(),
+                    quote: "four five".to_string()
+                },
+            ]
+        );
+
+        let mode = provider.next_text(
+            &Mode::Words {
+                n: 7,
+                s: "".to_string(),
+            },
+            &mut StdRng::seed_from_u64(0),
+        );
+        let words = mode.get_inner();
+        assert_eq!(words.split(' ').count(), 7);
+        assert!("one two three four five one two three four five".contains(&words));
+
+        assert!(TextProvider::from_text(" \n\n ", "empty").is_err());
+    }
+}
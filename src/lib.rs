@@ -1,23 +1,39 @@
 pub mod app;
 pub mod data_provider;
-use self::app::{Mode, ThemeName};
-use self::data_provider::Quote;
-use data_provider::Data;
+pub mod lint;
+use self::app::{Caret, Keybindings, Keyboard, Mode, ThemeName};
+use self::data_provider::{
+    Data, DataProvider, DirectoryProvider, Drill, HttpProvider, Lessons, Online, Practice, Quote,
+    Sampling, TextProvider,
+};
 
 use serde::{Deserialize, Serialize};
 
 use std::env::Args;
 use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use std::{fs, io};
 
-pub fn parse_args(mut args: Args) -> Result<(Data, Config), Box<dyn Error>> {
-    args.next();
+pub fn parse_args(args: Args) -> Result<(Box<dyn DataProvider>, Config), Box<dyn Error>> {
+    let mut args = args.skip(1).peekable();
+
+    if args.peek().map(|a| a.as_str()) == Some("lint") {
+        args.next();
+        lint::run(args);
+    }
 
     let mut words_file = None;
     let mut quotes_file = None;
 
     let mut config_path: PathBuf = Config::get_config_path();
+    let mut seed = None;
+    let mut online = false;
+    let mut dir = None;
+    let mut text_file = None;
+    let mut session = false;
 
     while let Some(arg) = args.next() {
         match arg.as_str() {
@@ -31,41 +47,110 @@ pub fn parse_args(mut args: Args) -> Result<(Data, Config), Box<dyn Error>> {
                     quotes_file = Some(f);
                 }
             }
-            //"--online" => return Data::new_online(words_file),
+            "--online" | "-o" => online = true,
+            "--session" => session = true,
+            "--dir" | "-d" => {
+                if let Some(d) = args.next() {
+                    dir = Some(PathBuf::from(d));
+                }
+            }
+            "--text" | "-t" => {
+                if let Some(f) = args.next() {
+                    text_file = Some(PathBuf::from(f));
+                }
+            }
             "--help" | "-h" => help(),
             "--config" | "-c" => {
                 if let Some(f) = args.next() {
                     config_path = PathBuf::from(f);
                 }
             }
+            "--seed" | "-s" => {
+                if let Some(s) = args.next() {
+                    seed = Some(s.parse::<u64>()?);
+                }
+            }
             _ => (),
         }
     }
 
-    let data = Data::new_offline(words_file, quotes_file)?;
+    let sources: Vec<&str> = [
+        (
+            words_file.is_some() || quotes_file.is_some(),
+            "--words/--quotes",
+        ),
+        (online, "--online"),
+        (dir.is_some(), "--dir"),
+        (text_file.is_some(), "--text"),
+    ]
+    .into_iter()
+    .filter_map(|(used, flag)| used.then_some(flag))
+    .collect();
+    if sources.len() > 1 {
+        return Err(format!("{} can't be used together", sources.join(" and ")).into());
+    }
+
+    let mut config = Config::new(config_path.as_path());
+    config.seed = seed;
+    config.start_session = session;
+
+    let data: Box<dyn DataProvider> = if let Some(f) = text_file {
+        Box::new(TextProvider::new(&f)?)
+    } else if let Some(d) = dir {
+        Box::new(DirectoryProvider::new(&d, &config.sampling)?)
+    } else if online {
+        Box::new(HttpProvider::new(&config.online, &config.sampling)?)
+    } else {
+        Box::new(Data::new_offline(words_file, quotes_file)?.with_sampling(&config.sampling)?)
+    };
 
-    Ok((data, Config::new(config_path.as_path())))
+    Ok((data, config))
 }
 
 fn help() {
     let help = [
-        "Usage: typing_test [-w <file>] [-q <file>] [-c <file>]\n",
-        "Test your typing speed from built-in words and quotes, or provide your own words and quotes. You can also choose to get quotes from scrapping popular quotes on the Internet (not implemented yet).\n",
+        "Usage: typing_test [-w <file>] [-q <file>] [-o] [-d <folder>] [-t <file>] [-c <file>] [-s <seed>] [--session]",
+        "       typing_test lint <file> [--dict <file>]\n",
+        "Test your typing speed from built-in words and quotes, or provide your own words and quotes. You can also choose to get words and quotes from the Internet.\n",
         "Controls:",
         "   <Tab>                   Cycle forward between buttons.",
         "   <Shift-Tab>             Cycle backward between buttons.",
+        "   <Esc>, <Tab> <Enter>    Restart the test.",
+        "   <Shift-Esc>             Go to the next test.",
+        "   <Ctrl>r, <Ctrl>n        Restart the test or go to the next one, r and n on the end screen.",
+        "   <Ctrl>t                 Pick a theme.",
+        "   <Ctrl>,                 Open the settings.",
+        "   <Ctrl><Shift>p          Open the command palette, to search every action, theme and mode.",
         "   <Enter>                 Click selected button. Alternatively, you can use your mouse to click on buttons.",
-        "   <Super>=                Increase font size.",
-        "   <Super>-                Decrease font size.",
-        "   <Super>0                Reset font size.\n",
+        "   <Shift-Enter>           End a zen test, which goes on until you stop.",
+        "   <Ctrl>=                 Increase font size.",
+        "   <Ctrl>-                 Decrease font size.",
+        "   <Ctrl>0                 Reset font size.",
+        "   Every shortcut can be changed in the [keybindings] section of the config, Ctrl is",
+        "   Super on macOS.\n",
         "Options:",
-        "   -w, --words <file>      Provide your own json words file.",
-        "                           The file consists of 1 json array of words.",
-        "   -q, --quotes <file>     Provide your own json quotes file.",
-        "                           The file is a json object with the sources as keys and an array of quotes as values.",
-        //"   -o, --online            Get quotes from the web. Must be connected to the internet.",
+        "   -w, --words <file>      Provide your own words file. Either a json array of words, most common",
+        "                           first, or of [word, frequency] pairs, a monkeytype language file,",
+        "                           a .txt file with one word per line or a .csv file of word[,frequency] rows.",
+        "   -q, --quotes <file>     Provide your own quotes file. Either a json object with the sources as keys",
+        "                           and an array of quotes as values, a monkeytype quotes file or a .csv",
+        "                           file of quote,source rows.",
+        "   -o, --online            Get words and quotes from the words_url and quotes_url of the [online]",
+        "                           section of the config. The last download is cached for offline use.",
+        "   -d, --dir <folder>      Use every json and csv words and quotes file of a folder. Text files are",
+        "                           words, one per line, or else quotes, one per paragraph.",
+        "   -t, --text <file>       Type through your own text file. Quote mode picks a paragraph.",
+        "                           Only one of -w and -q, -o, -d and -t can be used at a time.",
         "   -c, --config <file>     Provide custom config file. Default is ~/.typing_test.toml",
-        "   -h, --help              Print this help.",
+        "   -s, --seed <seed>       Generate the first text from this seed, shown on the end screen.",
+        "                           Everyone using the same seed and files gets the same texts.",
+        "       --session           Start a session of several tests in a row, set up in the [session]",
+        "                           section of the config, with a summary at the end. A session can also be",
+        "                           started from the command palette, or a key bound to it.",
+        "   -h, --help              Print this help.\n",
+        "Commands:",
+        "   lint <file>             Check a words or quotes file for problems without starting the test.",
+        "       --dict <file>       Also spellcheck against a dictionary with one word per line.",
     ]
     .join("\n");
 
@@ -87,6 +172,12 @@ pub struct Config {
     #[serde(skip)]
     pub config_file: PathBuf,
 
+    #[serde(skip)]
+    pub seed: Option<u64>,
+
+    #[serde(skip)]
+    pub start_session: bool,
+
     #[serde(default)]
     pub theme: ThemeName,
 
@@ -95,6 +186,45 @@ pub struct Config {
 
     #[serde(default)]
     pub mode: Mode,
+
+    #[serde(default)]
+    pub resume: bool,
+
+    #[serde(default)]
+    pub auto_restart_on_error: bool,
+
+    #[serde(default)]
+    pub sampling: Sampling,
+
+    #[serde(default)]
+    pub online: Online,
+
+    #[serde(default)]
+    pub lesson: Lessons,
+
+    #[serde(default)]
+    pub drill: Drill,
+
+    #[serde(default)]
+    pub practice: Practice,
+
+    #[serde(default)]
+    pub fail: Fail,
+
+    #[serde(default)]
+    pub session: Session,
+
+    #[serde(default)]
+    pub sprint: Sprints,
+
+    #[serde(default)]
+    pub keyboard: Keyboard,
+
+    #[serde(default)]
+    pub caret: Caret,
+
+    #[serde(default)]
+    pub keybindings: Keybindings,
     // extra_themes: HashMap<String, ThemeParams>,
 }
 
@@ -106,12 +236,27 @@ impl Default for Config {
     fn default() -> Self {
         Config {
             config_file: Config::get_config_path(),
+            seed: None,
+            start_session: false,
             theme: ThemeName::default(),
             font_size: 24.0,
             mode: Mode::Quote(Quote {
                 source: "".to_string(),
                 quote: "".to_string(),
             }),
+            resume: false,
+            auto_restart_on_error: false,
+            sampling: Sampling::default(),
+            online: Online::default(),
+            lesson: Lessons::default(),
+            drill: Drill::default(),
+            practice: Practice::default(),
+            fail: Fail::default(),
+            session: Session::default(),
+            sprint: Sprints::default(),
+            keyboard: Keyboard::default(),
+            caret: Caret::default(),
+            keybindings: Keybindings::default(),
         }
     }
 }
@@ -166,4 +311,322 @@ impl Config {
 
         config_path
     }
+
+    pub fn get_resume_path(&self) -> PathBuf {
+        self.config_file.with_extension("resume.toml")
+    }
+
+    pub fn get_history_path(&self) -> PathBuf {
+        self.config_file.with_extension("history.toml")
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Fail {
+    #[serde(default)]
+    pub sudden_death: bool,
+
+    #[serde(default)]
+    pub min_accuracy: Option<i32>,
+
+    #[serde(default)]
+    pub min_wpm: Option<f32>,
+
+    /// Seconds into a test before `min_accuracy` and `min_wpm` are checked.
+    #[serde(default = "default_grace")]
+    pub grace: f32,
+}
+
+fn default_grace() -> f32 {
+    5.0
+}
+
+impl Default for Fail {
+    fn default() -> Self {
+        Fail {
+            sudden_death: false,
+            min_accuracy: None,
+            min_wpm: None,
+            grace: default_grace(),
+        }
+    }
+}
+
+impl Fail {
+    pub fn check(
+        &self,
+        wpm: f32,
+        accuracy: i32,
+        mistakes: usize,
+        elapsed: Duration,
+    ) -> Option<String> {
+        if self.sudden_death && mistakes > 0 {
+            return Some("sudden death".to_string());
+        }
+
+        if elapsed.as_secs_f32() < self.grace {
+            return None;
+        }
+
+        match (self.min_accuracy, self.min_wpm) {
+            (Some(min), _) if accuracy < min => Some(format!("accuracy below {}%", min)),
+            (_, Some(min)) if wpm < min => Some(format!("below {} wpm", min)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Session {
+    #[serde(default = "default_tests")]
+    pub tests: usize,
+
+    /// Minutes a session lasts, used instead of `tests` when set. The test running when
+    /// the time is up is the last one.
+    #[serde(default)]
+    pub minutes: Option<u64>,
+
+    /// Modes the tests take turns in, any of `"words <n>"`, `"quote"`, `"daily"`,
+    /// `"zen"`, `"drill"` and `"lesson"`. The current mode is kept when empty.
+    #[serde(default)]
+    pub modes: Vec<String>,
+}
+
+fn default_tests() -> usize {
+    5
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session {
+            tests: default_tests(),
+            minutes: None,
+            modes: vec![],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Sprints {
+    #[serde(default = "default_sprint_seconds")]
+    pub seconds: u64,
+
+    #[serde(default = "default_rest")]
+    pub rest: u64,
+
+    #[serde(default = "default_rounds")]
+    pub rounds: usize,
+}
+
+fn default_sprint_seconds() -> u64 {
+    20
+}
+
+fn default_rest() -> u64 {
+    10
+}
+
+fn default_rounds() -> usize {
+    5
+}
+
+impl Default for Sprints {
+    fn default() -> Self {
+        Sprints {
+            seconds: default_sprint_seconds(),
+            rest: default_rest(),
+            rounds: default_rounds(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Summary {
+    pub mean: f32,
+    pub median: f32,
+    pub best: f32,
+}
+
+impl Summary {
+    pub fn new(runs: &[RunResult]) -> Option<Self> {
+        if runs.is_empty() {
+            return None;
+        }
+
+        let mut wpm: Vec<f32> = runs.iter().map(|r| r.wpm).collect();
+        wpm.sort_by(|a, b| a.total_cmp(b));
+
+        let mid = wpm.len() / 2;
+        let median = if wpm.len().is_multiple_of(2) {
+            (wpm[mid - 1] + wpm[mid]) / 2.0
+        } else {
+            wpm[mid]
+        };
+
+        Some(Summary {
+            mean: wpm.iter().sum::<f32>() / wpm.len() as f32,
+            median,
+            best: wpm[wpm.len() - 1],
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RunResult {
+    pub mode: String,
+    pub wpm: f32,
+    pub accuracy: i32,
+    /// Milliseconds the test took.
+    pub time: u64,
+    /// Seconds since the unix epoch when the test was finished.
+    pub finished: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed: Option<String>,
+}
+
+impl RunResult {
+    /// Adds the result to the end of the history file, without reading it first.
+    pub fn append(&self, filename: &Path) {
+        let entry = format!("\n[[runs]]\n{}", toml::to_string(&self).unwrap());
+
+        if let Err(e) = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(filename)
+            .and_then(|mut f| f.write_all(entry.as_bytes()))
+        {
+            println!("Can't save to history. {}", e);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Resume {
+    pub text: String,
+    pub typed: Vec<String>,
+    /// Milliseconds since the test started.
+    pub elapsed: u64,
+    pub samples: Vec<(u64, f32)>,
+}
+
+impl Resume {
+    pub fn load(filename: &Path) -> Option<Self> {
+        let s = fs::read_to_string(filename).ok()?;
+        toml::from_str(&s).ok()
+    }
+
+    pub fn save(&self, filename: &Path) {
+        if let Err(e) = fs::write(filename, toml::to_string(&self).unwrap()) {
+            println!("Can't save progress. {}", e);
+        };
+    }
+
+    pub fn clear(filename: &Path) {
+        if let Err(e) = fs::remove_file(filename) {
+            if e.kind() != io::ErrorKind::NotFound {
+                println!("Can't remove saved progress. {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fail_conditions() {
+        let secs = Duration::from_secs;
+        let fail = Fail {
+            min_accuracy: Some(90),
+            min_wpm: Some(30.0),
+            ..Fail::default()
+        };
+
+        assert_eq!(fail.check(10.0, 50, 3, secs(2)), None);
+        assert_eq!(fail.check(40.0, 95, 3, secs(10)), None);
+        assert_eq!(
+            fail.check(40.0, 85, 3, secs(10)),
+            Some("accuracy below 90%".to_string())
+        );
+        assert_eq!(
+            fail.check(20.0, 95, 3, secs(10)),
+            Some("below 30 wpm".to_string())
+        );
+
+        let sudden_death = Fail {
+            sudden_death: true,
+            ..Fail::default()
+        };
+        assert_eq!(sudden_death.check(100.0, 100, 0, secs(0)), None);
+        assert_eq!(
+            sudden_death.check(100.0, 100, 1, secs(0)),
+            Some("sudden death".to_string())
+        );
+    }
+
+    #[test]
+    fn history() {
+        #[derive(Deserialize)]
+        struct History {
+            runs: Vec<RunResult>,
+        }
+
+        let path =
+            std::env::temp_dir().join(format!("typing_test_history_{}.toml", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let runs = vec![
+            RunResult {
+                mode: "10 Words".to_string(),
+                wpm: 80.5,
+                accuracy: 97,
+                time: 7000,
+                finished: 1,
+                failed: None,
+            },
+            RunResult {
+                mode: "Daily 2026-10-19".to_string(),
+                wpm: 20.0,
+                accuracy: 60,
+                time: 3000,
+                finished: 2,
+                failed: Some("sudden death".to_string()),
+            },
+        ];
+
+        for run in &runs {
+            run.append(&path);
+        }
+
+        let history: History = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(history.runs, runs);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn session_summary() {
+        let run = |wpm| RunResult {
+            mode: "10 Words".to_string(),
+            wpm,
+            accuracy: 100,
+            time: 5000,
+            finished: 0,
+            failed: None,
+        };
+
+        assert_eq!(Summary::new(&[]), None);
+        assert_eq!(
+            Summary::new(&[run(60.0), run(40.0), run(80.0)]),
+            Some(Summary {
+                mean: 60.0,
+                median: 60.0,
+                best: 80.0
+            })
+        );
+        assert_eq!(
+            Summary::new(&[run(50.0), run(70.0)]).map(|s| s.median),
+            Some(60.0)
+        );
+    }
 }
@@ -0,0 +1,278 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use crate::app::LINE_LENGTH;
+use crate::data_provider::{parse, Format, Pack, Quote};
+
+#[derive(Debug, PartialEq)]
+pub struct Issue {
+    pub location: String,
+    pub message: String,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+pub fn run(mut args: impl Iterator<Item = String>) -> ! {
+    let mut file = None;
+    let mut dict = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dict" => dict = args.next(),
+            _ => file = Some(arg),
+        }
+    }
+
+    let Some(file) = file else {
+        println!("Usage: typing_test lint <file> [--dict <file>]");
+        process::exit(2);
+    };
+
+    match lint_file(Path::new(&file), dict.as_deref().map(Path::new)) {
+        Ok(issues) if issues.is_empty() => {
+            println!("{}: no issues", file);
+            process::exit(0);
+        }
+        Ok(issues) => {
+            for issue in &issues {
+                println!("{}", issue);
+            }
+            println!("{}: {} issues", file, issues.len());
+            process::exit(1);
+        }
+        Err(e) => {
+            println!("Can't lint {}. {}", file, e);
+            process::exit(2);
+        }
+    }
+}
+
+pub fn lint_file(path: &Path, dict: Option<&Path>) -> Result<Vec<Issue>, Box<dyn Error>> {
+    let s = fs::read_to_string(path)?;
+
+    let dict = match dict {
+        Some(d) => Some(load_dict(&fs::read_to_string(d)?)),
+        None => None,
+    };
+
+    Ok(match parse(&s, Format::detect(path, &s))? {
+        Pack::Words((words, _)) => lint_words(&words, dict.as_ref()),
+        Pack::Quotes(quotes) => lint_quotes(&quotes, dict.as_ref()),
+    })
+}
+
+pub fn load_dict(s: &str) -> HashSet<String> {
+    s.lines()
+        .map(|l| l.trim().to_lowercase())
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+pub fn lint_words(words: &[String], dict: Option<&HashSet<String>>) -> Vec<Issue> {
+    let mut issues = vec![];
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+
+    for (i, word) in words.iter().enumerate() {
+        let location = format!("word {} {:?}", i + 1, word);
+
+        check_text(word, &location, dict, &mut issues);
+
+        if word.contains(' ') {
+            issues.push(issue(&location, "is more than one word"));
+        }
+
+        if let Some(first) = seen.insert(word, i) {
+            issues.push(issue(
+                &location,
+                &format!("duplicate of word {}", first + 1),
+            ));
+        }
+    }
+
+    issues
+}
+
+pub fn lint_quotes(quotes: &[Quote], dict: Option<&HashSet<String>>) -> Vec<Issue> {
+    let mut issues = vec![];
+    let mut sources = HashSet::new();
+    let mut seen: HashMap<&str, String> = HashMap::new();
+
+    for (i, Quote { source, quote }) in quotes.iter().enumerate() {
+        if sources.insert(source) {
+            check_text(source, &format!("source {:?}", source), dict, &mut issues);
+        }
+
+        let location = format!("quote {} from {:?}", i + 1, source);
+
+        check_text(quote, &location, dict, &mut issues);
+
+        if quote == source {
+            issues.push(issue(&location, "is the same as its source"));
+        }
+
+        if let Some(first) = seen.insert(quote, location.clone()) {
+            issues.push(issue(&location, &format!("duplicate of {}", first)));
+        }
+    }
+
+    issues
+}
+
+fn check_text(text: &str, location: &str, dict: Option<&HashSet<String>>, issues: &mut Vec<Issue>) {
+    if text.is_empty() {
+        issues.push(issue(location, "is empty"));
+        return;
+    }
+
+    if text.contains("  ") {
+        issues.push(issue(location, "has a double space"));
+    }
+
+    if text.starts_with(' ') || text.ends_with(' ') {
+        issues.push(issue(location, "starts or ends with a space"));
+    }
+
+    if let Some(c) = text
+        .chars()
+        .find(|c| c.is_control() || c.is_whitespace() && *c != ' ')
+    {
+        issues.push(issue(
+            location,
+            &format!("has the non-printable character {:?}", c),
+        ));
+    }
+
+    for word in text.split(' ') {
+        if word.chars().count() > LINE_LENGTH {
+            issues.push(issue(
+                location,
+                &format!("{:?} is too long to fit on a line", word),
+            ));
+        }
+
+        if let Some(dict) = dict {
+            let bare = word
+                .trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase();
+
+            if !bare.is_empty() && !bare.chars().any(|c| c.is_numeric()) && !dict.contains(&bare) {
+                issues.push(issue(
+                    location,
+                    &format!("{:?} is not in the dictionary", bare),
+                ));
+            }
+        }
+    }
+}
+
+fn issue(location: &str, message: &str) -> Issue {
+    Issue {
+        location: location.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(issues: &[Issue]) -> Vec<&str> {
+        issues.iter().map(|i| &i.message[..]).collect()
+    }
+
+    #[test]
+    fn words() {
+        let words: Vec<String> = [
+            "fine",
+            "",
+            "fine",
+            "two  words",
+            "tab\there",
+            &"a".repeat(81),
+        ]
+        .iter()
+        .map(|w| w.to_string())
+        .collect();
+
+        let issues = lint_words(&words, None);
+
+        assert_eq!(
+            messages(&issues),
+            vec![
+                "is empty",
+                "duplicate of word 1",
+                "has a double space",
+                "is more than one word",
+                "has the non-printable character '\\t'",
+                &format!("{:?} is too long to fit on a line", "a".repeat(81)),
+            ]
+        );
+        assert_eq!(issues[1].location, "word 3 \"fine\"");
+    }
+
+    #[test]
+    fn quotes() {
+        let quotes: Vec<Quote> = [
+            ("Other", "Hello there."),
+            ("Someone", "Hello there."),
+            ("Someone", "Someone"),
+            ("Someone", " Hello there."),
+        ]
+        .iter()
+        .map(|(source, quote)| Quote {
+            source: source.to_string(),
+            quote: quote.to_string(),
+        })
+        .collect();
+
+        let issues = lint_quotes(&quotes, None);
+
+        assert_eq!(
+            issues.iter().map(|i| i.to_string()).collect::<Vec<_>>(),
+            vec![
+                "quote 2 from \"Someone\": duplicate of quote 1 from \"Other\"",
+                "quote 3 from \"Someone\": is the same as its source",
+                "quote 4 from \"Someone\": starts or ends with a space",
+            ]
+        );
+    }
+
+    #[test]
+    fn bundled_data() {
+        let words = parse(include_str!("data/english.json"), Format::Json);
+        let quotes = parse(include_str!("data/quotes.json"), Format::Json);
+
+        let Ok(Pack::Words((words, _))) = words else {
+            panic!("the bundled words aren't a words file");
+        };
+        let Ok(Pack::Quotes(quotes)) = quotes else {
+            panic!("the bundled quotes aren't a quotes file");
+        };
+
+        assert_eq!(lint_words(&words, None), vec![]);
+        assert_eq!(lint_quotes(&quotes, None), vec![]);
+    }
+
+    #[test]
+    fn spelling() {
+        let dict = load_dict("hello\nThere\n");
+        let words = vec![
+            "Hello,".to_string(),
+            "there!".to_string(),
+            "thier".to_string(),
+        ];
+
+        assert_eq!(
+            messages(&lint_words(&words, Some(&dict))),
+            vec!["\"thier\" is not in the dictionary"]
+        );
+    }
+}
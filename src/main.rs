@@ -1,12 +1,19 @@
 use macroquad::window::Conf;
+use macroquad::Window;
 use std::error::Error;
 use typing_test::app::App;
 
-#[macroquad::main(window_conf)]
-async fn main() -> Result<(), Box<dyn Error>> {
+fn main() -> Result<(), Box<dyn Error>> {
+    // parse before opening the window so that `--help` and `lint` work without a display
     let (data, config) = typing_test::parse_args(std::env::args())?;
-    let mut app = App::new(data, config).await;
-    app.main_loop().await?;
+
+    Window::from_config(window_conf(), async move {
+        let mut app = App::new(data, config).await;
+
+        if let Err(e) = app.main_loop().await {
+            println!("{}", e);
+        }
+    });
 
     Ok(())
 }